@@ -2,24 +2,62 @@
 //! Uses bellman library for circuit construction
 
 use bellman::{
-    Circuit, ConstraintSystem, SynthesisError, 
+    Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable,
     groth16::{Parameters, Proof},
 };
 use bls12_381::{Bls12, Scalar};
 use ff::PrimeField;
+use poe_zkbtc_minter::{
+    public_input_digest, public_input_from_u64, PUBLIC_INPUT_ANCHOR, PUBLIC_INPUT_CLASSIFICATION, PUBLIC_INPUT_CURRENT_TIME,
+    PUBLIC_INPUT_DEVICE, PUBLIC_INPUT_ENERGY, PUBLIC_INPUT_MAX_AGE, PUBLIC_INPUT_ORACLE_VALID,
+    PUBLIC_INPUT_TIMESTAMP,
+};
+use std::fs::File;
+use std::io::BufReader;
 use std::marker::PhantomData;
+use std::path::Path;
+
+/// Number of public inputs the circuit allocates (fields of `PoEPublicInputs`),
+/// laid out by the minter's `PUBLIC_INPUT_*` positions
+pub use poe_zkbtc_minter::POE_PUBLIC_INPUT_COUNT;
 
 /// Public inputs to the circuit
 #[derive(Clone)]
 pub struct PoEPublicInputs {
-    /// Hash of device ID
+    /// Hash of device ID (`public_input_digest` of the device ID)
     pub device_id_hash: [u8; 32],
     /// Energy generated (in Wh)
     pub energy_wh: u64,
     /// Timestamp
     pub timestamp: u64,
+    /// Prover's current time (ms); the reading must not be newer. The minter
+    /// rejects proofs whose current time drifts too far from block time.
+    pub current_time: u64,
+    /// Maximum age of the reading relative to `current_time` (ms)
+    pub max_age_ms: u64,
     /// Oracle signature validity
     pub oracle_valid: bool,
+    /// Device's region and classification version when the proof is made,
+    /// encoded with the minter's `classification_digest`
+    pub classification: [u8; 32],
+    /// `public_input_digest` of a recent block hash the proof commits to
+    pub anchor: [u8; 32],
+}
+
+impl PoEPublicInputs {
+    /// Encode in the minter's `ZkMintProof::public_inputs` layout
+    pub fn to_public_inputs(&self) -> Vec<[u8; 32]> {
+        let mut inputs = vec![[0u8; 32]; POE_PUBLIC_INPUT_COUNT];
+        inputs[PUBLIC_INPUT_ENERGY] = public_input_from_u64(self.energy_wh);
+        inputs[PUBLIC_INPUT_DEVICE] = self.device_id_hash;
+        inputs[PUBLIC_INPUT_ANCHOR] = self.anchor;
+        inputs[PUBLIC_INPUT_CURRENT_TIME] = public_input_from_u64(self.current_time);
+        inputs[PUBLIC_INPUT_CLASSIFICATION] = self.classification;
+        inputs[PUBLIC_INPUT_TIMESTAMP] = public_input_from_u64(self.timestamp);
+        inputs[PUBLIC_INPUT_MAX_AGE] = public_input_from_u64(self.max_age_ms);
+        inputs[PUBLIC_INPUT_ORACLE_VALID] = public_input_from_u64(self.oracle_valid as u64);
+        inputs
+    }
 }
 
 /// Private inputs (witnesses)
@@ -39,7 +77,11 @@ pub struct PoECircuit<F: PrimeField> {
     pub device_id_hash: Option<[u8; 32]>,
     pub energy_wh: Option<u64>,
     pub timestamp: Option<u64>,
+    pub current_time: Option<u64>,
+    pub max_age_ms: Option<u64>,
     pub oracle_valid: Option<bool>,
+    pub classification: Option<[u8; 32]>,
+    pub anchor: Option<[u8; 32]>,
     
     // Private inputs
     pub device_id: Option<[u8; 32]>,
@@ -51,16 +93,29 @@ pub struct PoECircuit<F: PrimeField> {
 
 impl<F: PrimeField> Circuit<F> for PoECircuit<F> {
     fn synthesize<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        // Public inputs, allocated strictly in the minter's index order
+        let mut values: [Option<F>; POE_PUBLIC_INPUT_COUNT] = [None; POE_PUBLIC_INPUT_COUNT];
+        values[PUBLIC_INPUT_ENERGY] = self.energy_wh.map(F::from);
+        values[PUBLIC_INPUT_DEVICE] = self.device_id_hash.map(|hash| hash_to_field::<F>(&hash));
+        values[PUBLIC_INPUT_ANCHOR] = self.anchor.map(|anchor| hash_to_field::<F>(&anchor));
+        values[PUBLIC_INPUT_CURRENT_TIME] = self.current_time.map(F::from);
+        values[PUBLIC_INPUT_CLASSIFICATION] = self.classification.map(|digest| hash_to_field::<F>(&digest));
+        values[PUBLIC_INPUT_TIMESTAMP] = self.timestamp.map(F::from);
+        values[PUBLIC_INPUT_MAX_AGE] = self.max_age_ms.map(F::from);
+        values[PUBLIC_INPUT_ORACLE_VALID] = self.oracle_valid.map(|v| if v { F::one() } else { F::zero() });
+        
+        let mut inputs = Vec::with_capacity(POE_PUBLIC_INPUT_COUNT);
+        for (index, value) in values.iter().enumerate() {
+            inputs.push(cs.alloc_input(
+                || format!("public input {}", index),
+                || value.ok_or(SynthesisError::AssignmentMissing),
+            )?);
+        }
+        
         // 1. Verify device ID hash matches private device ID
-        let device_id_var = cs.alloc_input(|| "device_id", || {
+        let device_id_var = cs.alloc(|| "device_id", || {
             self.device_id
-                .map(|id| hash_to_field::<F>(&id))
-                .ok_or(SynthesisError::AssignmentMissing)
-        })?;
-        
-        let device_id_hash_var = cs.alloc_input(|| "device_id_hash", || {
-            self.device_id_hash
-                .map(|hash| hash_to_field::<F>(&hash))
+                .map(|id| hash_to_field::<F>(&public_input_digest(&id)))
                 .ok_or(SynthesisError::AssignmentMissing)
         })?;
         
@@ -69,77 +124,146 @@ impl<F: PrimeField> Circuit<F> for PoECircuit<F> {
             || "device_id_hash_constraint",
             |lc| lc + device_id_var,
             |lc| lc + CS::one(),
-            |lc| lc + device_id_hash_var,
+            |lc| lc + inputs[PUBLIC_INPUT_DEVICE],
         );
         
-        // 2. Verify energy calculation from sensor data
-        let energy_var = cs.alloc_input(|| "energy_wh", || {
-            self.energy_wh
-                .map(|e| F::from(e))
+        // 2. Verify energy calculation from sensor data (simplified: the weighted
+        // sum is computed natively and carried as a witness)
+        let calculated_energy_var = cs.alloc(|| "calculated_energy", || {
+            self.sensor_data
+                .as_ref()
+                .map(|data| {
+                    data.iter().enumerate().fold(F::zero(), |sum, (i, &reading)| {
+                        sum + F::from((i + 1) as u64) * F::from(reading)
+                    })
+                })
                 .ok_or(SynthesisError::AssignmentMissing)
         })?;
         
-        // Simulate energy calculation from sensor data
-        let calculated_energy_var = if let Some(data) = &self.sensor_data {
-            // Sum sensor readings with some coefficients
-            let mut sum = F::zero();
-            for (i, &reading) in data.iter().enumerate() {
-                let coeff = F::from((i + 1) as u64); // Simplified coefficient
-                sum += coeff * F::from(reading);
-            }
-            sum
-        } else {
-            F::zero()
-        };
-        
         // Constraint: calculated energy == claimed energy
         cs.enforce(
             || "energy_constraint",
-            |lc| lc + energy_var,
+            |lc| lc + inputs[PUBLIC_INPUT_ENERGY],
             |lc| lc + CS::one(),
             |lc| lc + calculated_energy_var,
         );
         
-        // 3. Verify timestamp is recent (within 24 hours)
-        let timestamp_var = cs.alloc_input(|| "timestamp", || {
-            self.timestamp
-                .map(|t| F::from(t))
-                .ok_or(SynthesisError::AssignmentMissing)
-        })?;
+        // 3. Verify timestamp is fresh: current_time - max_age_ms <= timestamp <= current_time
+        let timestamp_var = inputs[PUBLIC_INPUT_TIMESTAMP];
+        let current_time_var = inputs[PUBLIC_INPUT_CURRENT_TIME];
+        let max_age_var = inputs[PUBLIC_INPUT_MAX_AGE];
         
-        // Current time constant (would be provided as public input in reality)
-        let current_time = F::from(1_700_000_000_000u64); // Example
+        // Each difference must decompose into 64 bits, i.e. be non-negative.
+        // An out-of-window reading wraps, and the wrapped bits can't satisfy the sum.
+        let not_future = self.current_time
+            .zip(self.timestamp)
+            .map(|(now, t)| now.wrapping_sub(t));
+        enforce_u64_range(
+            cs.namespace(|| "timestamp_not_future"),
+            not_future,
+            |lc| lc + current_time_var - timestamp_var,
+        )?;
         
-        // Constraint: timestamp <= current_time
-        cs.enforce(
-            || "timestamp_constraint",
-            |lc| lc + current_time,
-            |lc| lc + CS::one(),
-            |lc| lc + timestamp_var,
-        );
+        let not_stale = self.timestamp
+            .zip(self.max_age_ms)
+            .zip(self.current_time)
+            .map(|((t, age), now)| t.wrapping_add(age).wrapping_sub(now));
+        enforce_u64_range(
+            cs.namespace(|| "timestamp_not_stale"),
+            not_stale,
+            |lc| lc + timestamp_var + max_age_var - current_time_var,
+        )?;
         
         // 4. Verify oracle signature (simplified)
-        let oracle_valid_var = cs.alloc_input(|| "oracle_valid", || {
-            self.oracle_valid
-                .map(|v| if v { F::one() } else { F::zero() })
-                .ok_or(SynthesisError::AssignmentMissing)
-        })?;
-        
         // Constraint: oracle_valid must be 1 (true)
         cs.enforce(
             || "oracle_constraint",
-            |lc| lc + oracle_valid_var,
+            |lc| lc + inputs[PUBLIC_INPUT_ORACLE_VALID],
             |lc| lc + CS::one(),
             |lc| lc + CS::one(),
         );
         
+        // 5. Bind the anchor and classification: inputs no constraint touches
+        // would verify with any value
+        bind_input(cs.namespace(|| "anchor"), inputs[PUBLIC_INPUT_ANCHOR], values[PUBLIC_INPUT_ANCHOR])?;
+        bind_input(
+            cs.namespace(|| "classification"),
+            inputs[PUBLIC_INPUT_CLASSIFICATION],
+            values[PUBLIC_INPUT_CLASSIFICATION],
+        )?;
+        
         Ok(())
     }
 }
 
-/// Helper function to hash bytes to field element
-fn hash_to_field<F: PrimeField>(data: &[u8]) -> F {
-    // Simplified hash - in production use Poseidon or MiMC
+/// Tie a public input into the constraint system by squaring it into a witness
+fn bind_input<F, CS>(mut cs: CS, input: Variable, value: Option<F>) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let square = cs.alloc(|| "square", || {
+        value.map(|v| v.square()).ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    
+    // Constraint: input * input == square
+    cs.enforce(
+        || "binding",
+        |lc| lc + input,
+        |lc| lc + input,
+        |lc| lc + square,
+    );
+    
+    Ok(())
+}
+
+/// Constrain `value_lc` to equal `value`'s 64-bit decomposition, proving it lies in [0, 2^64)
+fn enforce_u64_range<F, CS, L>(
+    mut cs: CS,
+    value: Option<u64>,
+    value_lc: L,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+    L: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+{
+    let mut bits = LinearCombination::<F>::zero();
+    let mut coeff = F::one();
+    
+    for i in 0..64 {
+        let bit = cs.alloc(|| format!("bit {}", i), || {
+            value
+                .map(|v| if (v >> i) & 1 == 1 { F::one() } else { F::zero() })
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        
+        // Constraint: bit * (1 - bit) == 0
+        cs.enforce(
+            || format!("bit {} boolean", i),
+            |lc| lc + bit,
+            |lc| lc + CS::one() - bit,
+            |lc| lc,
+        );
+        
+        bits = bits + (coeff, bit);
+        coeff = coeff.double();
+    }
+    
+    // Constraint: sum(bit_i * 2^i) == value
+    cs.enforce(
+        || "decomposition",
+        |_| bits,
+        |lc| lc + CS::one(),
+        value_lc,
+    );
+    
+    Ok(())
+}
+
+/// Decode a 32-byte public input into a field element. Bytes are read
+/// big-endian, matching the minter's `public_input_digest`/`public_input_from_u64`.
+fn hash_to_field<F: PrimeField>(data: &[u8; 32]) -> F {
     let mut sum = F::zero();
     for &byte in data {
         sum = sum * F::from(256u64) + F::from(byte as u64);
@@ -147,6 +271,35 @@ fn hash_to_field<F: PrimeField>(data: &[u8]) -> F {
     sum
 }
 
+/// Load Groth16 parameters from disk and check they fit this circuit
+pub fn load_params<P: AsRef<Path>>(path: P) -> Result<Parameters<Bls12>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open params: {}", e))?;
+    let params = Parameters::<Bls12>::read(BufReader::new(file), true)
+        .map_err(|e| format!("Failed to read params: {}", e))?;
+    
+    validate_params(&params)?;
+    
+    Ok(params)
+}
+
+/// Check parameters were generated for this circuit's public-input shape
+pub fn validate_params(params: &Parameters<Bls12>) -> Result<(), String> {
+    // The verifying key has one IC element per public input plus the constant term
+    let inputs = params.vk.ic.len().saturating_sub(1);
+    if inputs != POE_PUBLIC_INPUT_COUNT {
+        return Err(format!(
+            "Parameter mismatch: expected {} public inputs, found {}",
+            POE_PUBLIC_INPUT_COUNT, inputs
+        ));
+    }
+    
+    if params.h.is_empty() || params.l.is_empty() {
+        return Err("Parameter mismatch: missing constraint bases".into());
+    }
+    
+    Ok(())
+}
+
 /// Generate proof for PoE data
 pub fn generate_poe_proof(
     params: &Parameters<Bls12>,
@@ -157,7 +310,11 @@ pub fn generate_poe_proof(
         device_id_hash: Some(public.device_id_hash),
         energy_wh: Some(public.energy_wh),
         timestamp: Some(public.timestamp),
+        current_time: Some(public.current_time),
+        max_age_ms: Some(public.max_age_ms),
         oracle_valid: Some(public.oracle_valid),
+        classification: Some(public.classification),
+        anchor: Some(public.anchor),
         device_id: Some(private.device_id),
         sensor_data: Some(private.sensor_data),
         oracle_sig: Some(private.oracle_sig),
@@ -166,3 +323,1166 @@ pub fn generate_poe_proof(
     
     bellman::groth16::create_random_proof(circuit, params, &mut rand::thread_rng())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::groth16::generate_random_parameters;
+    use charms_sdk::crypto::sha256;
+    use charms_sdk::prelude::Encode;
+    use charms_sdk::testing;
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+    use poe_zkbtc_minter::poe_zkbtc_minter::PoEzkBTCMinter;
+    use poe_zkbtc_minter::{
+        classification_digest, group_signing_bytes, packet_signing_bytes, AdminAction, AdminActionEvent,
+        AssetsBridgedEvent, BatchMintedEvent, BridgeInstruction, EmergencyOracleAddedEvent, EnergySource, EnergyUnit,
+        MintFlaggedEvent, MinterError, Network, OracleRole, PoEPacket, VerificationMode, ZkMintProof,
+        BRIDGE_INSTRUCTION_VERSION, DEFAULT_BRIDGE_DEADLINE_SECS, DEFAULT_EMERGENCY_ORACLE_TTL, NULLIFIER_TREE_DEPTH,
+        TOKEN_PRICE_USD, WH_PER_TOKEN,
+    };
+    use std::cell::Cell;
+    use std::rc::Rc;
+    
+    const ADMIN: &str = "dao";
+    const PROSUMER: &str = "prosumer";
+    const DEVICE: [u8; 32] = [1u8; 32];
+    const REGION: u16 = 3;
+    const VK_HASH: [u8; 32] = [9u8; 32];
+    const NOW_SECS: u64 = 1_700_000_000;
+    const ANCHOR_HEIGHT: u64 = 100;
+    const ANCHOR_HASH: [u8; 32] = [5u8; 32];
+    const MAX_AGE_MS: u64 = poe_zkbtc_minter::DEFAULT_MAX_PROOF_AGE_MS;
+    const VAULT: &str = "grail-vault";
+    
+    fn blank_circuit() -> PoECircuit<Scalar> {
+        PoECircuit {
+            device_id_hash: None,
+            energy_wh: None,
+            timestamp: None,
+            current_time: None,
+            max_age_ms: None,
+            oracle_valid: None,
+            classification: None,
+            anchor: None,
+            device_id: None,
+            sensor_data: None,
+            oracle_sig: None,
+            _marker: PhantomData,
+        }
+    }
+    
+    fn params() -> Parameters<Bls12> {
+        generate_random_parameters::<Bls12, _, _>(blank_circuit(), &mut rand::thread_rng()).unwrap()
+    }
+    
+    /// Minter with the circuit's verifying key registered and `DEVICE` certified
+    fn minter(params: &Parameters<Bls12>) -> PoEzkBTCMinter {
+        testing::reset();
+        testing::set_block_timestamp(NOW_SECS);
+        testing::set_block_height(ANCHOR_HEIGHT);
+        testing::set_block_hash(ANCHOR_HEIGHT, ANCHOR_HASH);
+        
+        testing::set_caller(ADMIN);
+        let mut minter = PoEzkBTCMinter::new(ADMIN.into(), "treasury".into(), Network::Testnet, VerificationMode::Full);
+        let mut vk = Vec::new();
+        params.vk.write(&mut vk).unwrap();
+        minter.register_verifying_key(VK_HASH, vk).unwrap();
+        
+        testing::set_caller(PROSUMER);
+        minter.register_device(DEVICE, REGION, EnergyUnit::Wh).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(DEVICE, 1_000_000_000, 1).unwrap();
+        minter
+    }
+    
+    fn packet(energy_wh: u64) -> PoEPacket {
+        PoEPacket {
+            device_id: DEVICE,
+            timestamp: NOW_SECS * 1_000 - 60_000,
+            energy_wh,
+            cumulative_energy: energy_wh as u128,
+            energy_source: EnergySource::Solar,
+            grid_id: 1,
+            signature: [0u8; 64],
+            oracle_id: [0u8; 32],
+        }
+    }
+    
+    fn keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+    
+    /// Whitelisted oracle that signs test packets
+    fn oracle() -> Keypair {
+        keypair(4)
+    }
+    
+    /// Non-membership path of any nullifier in the empty spent-nullifier tree
+    fn empty_tree_siblings() -> Vec<[u8; 32]> {
+        let mut node = [0u8; 32];
+        let mut siblings = Vec::with_capacity(NULLIFIER_TREE_DEPTH);
+        for _ in 0..NULLIFIER_TREE_DEPTH {
+            siblings.push(node);
+            let mut preimage = [0u8; 64];
+            preimage[..32].copy_from_slice(&node);
+            preimage[32..].copy_from_slice(&node);
+            node = sha256(&preimage);
+        }
+        siblings
+    }
+    
+    fn signed(mut packet: PoEPacket) -> PoEPacket {
+        let oracle = oracle();
+        packet.oracle_id = oracle.public.to_bytes();
+        packet.signature = oracle.sign(&packet_signing_bytes(&packet)).to_bytes();
+        packet
+    }
+    
+    fn public_inputs(packet: &PoEPacket, classification: [u8; 32]) -> PoEPublicInputs {
+        PoEPublicInputs {
+            device_id_hash: public_input_digest(&packet.device_id),
+            energy_wh: packet.energy_wh,
+            timestamp: packet.timestamp,
+            current_time: NOW_SECS * 1_000,
+            max_age_ms: MAX_AGE_MS,
+            oracle_valid: true,
+            classification,
+            anchor: public_input_digest(&ANCHOR_HASH),
+        }
+    }
+    
+    fn prove(params: &Parameters<Bls12>, public: PoEPublicInputs) -> ZkMintProof {
+        prove_for(params, DEVICE, public)
+    }
+    
+    fn prove_for(params: &Parameters<Bls12>, device_id: [u8; 32], public: PoEPublicInputs) -> ZkMintProof {
+        let private = PoEPrivateInputs {
+            device_id,
+            // A single reading carries weight 1, so it is the energy itself
+            sensor_data: vec![public.energy_wh],
+            oracle_sig: [0u8; 64],
+        };
+        let public_inputs = public.to_public_inputs();
+        
+        let proof = generate_poe_proof(params, public, private).unwrap();
+        let mut bytes = Vec::new();
+        proof.write(&mut bytes).unwrap();
+        
+        ZkMintProof { proof: bytes, public_inputs, vk_hash: VK_HASH }
+    }
+    
+    #[test]
+    fn circuit_proof_verifies_on_minter() {
+        let params = params();
+        let minter = minter(&params);
+        let packet = packet(5_000);
+        
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        assert_eq!(proof.public_inputs.len(), POE_PUBLIC_INPUT_COUNT);
+        assert_eq!(minter.verify_proof_only(packet, proof), Ok(()));
+    }
+    
+    #[test]
+    fn tampered_public_input_fails_on_minter() {
+        let params = params();
+        let minter = minter(&params);
+        let reading = packet(5_000);
+        
+        let mut proof = prove(&params, public_inputs(&reading, classification_digest(REGION, 0)));
+        proof.public_inputs[PUBLIC_INPUT_ENERGY] = public_input_from_u64(6_000);
+        assert_eq!(
+            minter.verify_proof_only(packet(6_000), proof),
+            Err(MinterError::InvalidProof("Proof verification failed".into()))
+        );
+    }
+    
+    #[test]
+    fn fresh_anchor_accepted_and_stale_anchor_rejected() {
+        let params = params();
+        let minter = minter(&params);
+        let packet = packet(5_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        
+        // Still within `DEFAULT_MAX_ANCHOR_DEPTH` blocks of the anchor
+        testing::set_block_height(ANCHOR_HEIGHT + poe_zkbtc_minter::DEFAULT_MAX_ANCHOR_DEPTH);
+        assert_eq!(minter.verify_proof_only(packet.clone(), proof.clone()), Ok(()));
+        
+        testing::set_block_height(ANCHOR_HEIGHT + poe_zkbtc_minter::DEFAULT_MAX_ANCHOR_DEPTH + 1);
+        assert_eq!(
+            minter.verify_proof_only(packet, proof),
+            Err(MinterError::StaleAnchor("Stale proof anchor".into()))
+        );
+    }
+    
+    /// Minter linked to a vault that can back `capacity` more tokens; returns the
+    /// running total the vault has recorded as minted
+    fn link_vault(minter: &mut PoEzkBTCMinter, capacity: u64) -> Rc<Cell<u64>> {
+        minter.set_vault(VAULT.into()).unwrap();
+        testing::mock_view::<(), u64>(VAULT, "mint_capacity", move |_| Ok(capacity));
+        
+        let recorded = Rc::new(Cell::new(0u64));
+        let sink = recorded.clone();
+        testing::mock_call::<u64, ()>(VAULT, "record_mint", move |amount| {
+            sink.set(sink.get() + amount);
+            Ok(())
+        });
+        recorded
+    }
+    
+    #[test]
+    fn mint_records_supply_on_vault() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        let recorded = link_vault(&mut minter, 3);
+        
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(3));
+        assert_eq!(recorded.get(), 3);
+    }
+    
+    #[test]
+    fn mint_beyond_vault_growth_cap_is_rejected() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        let recorded = link_vault(&mut minter, 2);
+        
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe(packet, proof, [8u8; 32]),
+            Err("Mint exceeds eligible backing".into())
+        );
+        assert_eq!(recorded.get(), 0);
+        assert_eq!(minter.total_minted, 0);
+    }
+    
+    #[test]
+    fn proof_timestamp_must_match_packet() {
+        let params = params();
+        let minter = minter(&params);
+        let packet = packet(5_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        
+        let mut older = packet.clone();
+        older.timestamp -= 1_000;
+        assert_eq!(
+            minter.verify_proof_only(older, proof),
+            Err(MinterError::InvalidProof("Proof doesn't match packet timestamp".into()))
+        );
+    }
+    
+    #[test]
+    fn proof_max_age_must_match_minter_policy() {
+        let params = params();
+        let mut minter = minter(&params);
+        let packet = packet(5_000);
+        
+        // A prover widening its own freshness window
+        let mut public = public_inputs(&packet, classification_digest(REGION, 0));
+        public.max_age_ms = 30 * MAX_AGE_MS;
+        let proof = prove(&params, public);
+        assert_eq!(
+            minter.verify_proof_only(packet.clone(), proof.clone()),
+            Err(MinterError::ProofTime("Proof max age doesn't match policy".into()))
+        );
+        
+        minter.set_max_proof_age(30 * MAX_AGE_MS).unwrap();
+        assert_eq!(minter.verify_proof_only(packet, proof), Ok(()));
+    }
+    
+    #[test]
+    fn reclassification_invalidates_earlier_proofs() {
+        let params = params();
+        let mut minter = minter(&params);
+        let packet = packet(5_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        assert_eq!(minter.verify_proof_only(packet.clone(), proof.clone()), Ok(()));
+        
+        minter.reclassify_device(DEVICE, REGION + 1).unwrap();
+        assert_eq!(
+            minter.verify_proof_only(packet.clone(), proof),
+            Err(MinterError::Classification("Proof against outdated device classification".into()))
+        );
+        
+        let reproved = prove(&params, public_inputs(&packet, classification_digest(REGION + 1, 1)));
+        assert_eq!(minter.verify_proof_only(packet, reproved), Ok(()));
+    }
+    
+    #[test]
+    fn minted_proof_reports_already_used() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        minter.mint_with_poe(packet.clone(), proof.clone(), [8u8; 32]).unwrap();
+        
+        assert_eq!(minter.verify_proof_only(packet, proof), Err(MinterError::ProofAlreadyUsed));
+    }
+    
+    #[test]
+    fn accumulator_mints_need_a_non_membership_path() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_nullifier_accumulator(true).unwrap();
+        
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe(packet.clone(), proof.clone(), [8u8; 32]),
+            Err("Non-membership proof required".into())
+        );
+        assert_eq!(
+            minter.mint_with_nullifier_proof(packet.clone(), proof.clone(), [8u8; 32], empty_tree_siblings()),
+            Ok(3)
+        );
+        
+        // The nullifier is now a member, so the empty-tree path no longer proves absence
+        assert_eq!(
+            minter.mint_with_nullifier_proof(packet, proof, [8u8; 32], empty_tree_siblings()),
+            Err("Proof already used".into())
+        );
+    }
+    
+    #[test]
+    fn quorum_mints_carry_the_non_membership_path() {
+        let params = params();
+        let mut minter = minter(&params);
+        let (first, second) = (oracle(), keypair(6));
+        minter.add_oracle(first.public.to_bytes()).unwrap();
+        minter.add_oracle(second.public.to_bytes()).unwrap();
+        minter.set_oracle_threshold(2).unwrap();
+        minter.set_nullifier_accumulator(true).unwrap();
+        
+        let packet = packet(3_000_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let message = packet_signing_bytes(&packet);
+        let signatures = vec![
+            (first.public.to_bytes(), first.sign(&message).to_bytes()),
+            (second.public.to_bytes(), second.sign(&message).to_bytes()),
+        ];
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe_quorum(packet, signatures, proof, [8u8; 32], Some(empty_tree_siblings())),
+            Ok(3)
+        );
+    }
+    
+    #[test]
+    fn failed_batch_items_only_cache_their_proof() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        let first = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&first, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        minter.mint_with_poe(first, proof, [8u8; 32]).unwrap();
+        assert_eq!((minter.proof_cache_order.len(), minter.utxo_commitments.len()), (1, 1));
+        
+        // Verifies fine, but its counter doesn't advance past the first mint
+        let stale = signed(packet(2_000_000));
+        let proof = prove(&params, public_inputs(&stale, classification_digest(REGION, 0)));
+        assert_eq!(
+            minter.mint_batch(vec![(stale, proof, [9u8; 32], None)], 1),
+            Ok(vec![Err("Stale cumulative energy".into())])
+        );
+        
+        // The proof did verify, so it is cached for a retry; the payment isn't committed
+        assert_eq!((minter.proof_cache_order.len(), minter.utxo_commitments.len()), (2, 1));
+        assert_eq!(minter.total_minted, 3);
+    }
+    
+    #[test]
+    fn batch_rejects_zero_max_items() {
+        let params = params();
+        let mut minter = minter(&params);
+        
+        let packet = packet(3_000_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        assert_eq!(
+            minter.mint_batch(vec![(packet, proof, [8u8; 32], None)], 0),
+            Err("max_items must be non-zero".into())
+        );
+    }
+    
+    #[test]
+    fn meter_reset_needs_a_re_anchored_counter() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        let before = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&before, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        minter.mint_with_poe(before, proof, [8u8; 32]).unwrap();
+        
+        // The replaced meter counts up from zero again
+        let after = signed(packet(1_000_000));
+        let proof = prove(&params, public_inputs(&after, classification_digest(REGION, 0)));
+        assert_eq!(
+            minter.mint_with_poe(after.clone(), proof.clone(), [9u8; 32]),
+            Err("Stale cumulative energy".into())
+        );
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(minter.reset_cumulative(DEVICE, 0, [0u8; 32]), Err("Missing reset evidence".into()));
+        minter.reset_cumulative(DEVICE, 0, [2u8; 32]).unwrap();
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(after, proof, [9u8; 32]), Ok(1));
+        assert_eq!(minter.total_minted, 4);
+    }
+    
+    #[test]
+    fn paused_device_is_blocked_while_others_mint() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        let other_device = [2u8; 32];
+        testing::set_caller(PROSUMER);
+        minter.register_device(other_device, REGION, EnergyUnit::Wh).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(other_device, 1_000_000_000, 1).unwrap();
+        minter.pause_device(DEVICE).unwrap();
+        
+        let paused = signed(packet(3_000_000));
+        let paused_proof = prove(&params, public_inputs(&paused, classification_digest(REGION, 0)));
+        let other = signed(PoEPacket { device_id: other_device, ..packet(2_000_000) });
+        let other_proof = prove_for(&params, other_device, public_inputs(&other, classification_digest(REGION, 0)));
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe(paused.clone(), paused_proof.clone(), [8u8; 32]),
+            Err("Device minting paused".into())
+        );
+        assert_eq!(minter.mint_with_poe(other, other_proof, [9u8; 32]), Ok(2));
+        
+        testing::set_caller(ADMIN);
+        minter.unpause_device(DEVICE).unwrap();
+        assert_eq!(minter.unpause_device(DEVICE), Err("Device not paused".into()));
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(paused, paused_proof, [8u8; 32]), Ok(3));
+    }
+    
+    #[test]
+    fn revoked_payment_flags_the_mints_it_backed() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        let (revoked, sound) = ([8u8; 32], [9u8; 32]);
+        
+        // Two mints on the revoked payment, one on another
+        testing::set_caller(PROSUMER);
+        for (cumulative, utxo) in [(3_000_000u128, revoked), (5_000_000, revoked), (7_000_000, sound)] {
+            let packet = signed(PoEPacket { cumulative_energy: cumulative, ..packet(2_000_000) });
+            let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+            minter.mint_with_poe(packet, proof, utxo).unwrap();
+        }
+        assert!(minter.flag_payment_mints(revoked, "fake SPV proof".into()).is_err());
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(minter.flag_payment_mints(revoked, "fake SPV proof".into()), Ok(2));
+        assert_eq!(minter.flagged_mints.iter().count(), 2);
+        
+        let flagged = testing::emitted_events::<MintFlaggedEvent>("MintFlagged");
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.iter().all(|event| event.txid == revoked && event.reason == "fake SPV proof"));
+        
+        assert_eq!(minter.flag_payment_mints(sound, "audit".into()), Ok(1));
+        assert_eq!(minter.flagged_mints.iter().count(), 3);
+    }
+    
+    /// Key the minter caches a verified (proof, packet) pair under
+    fn cache_key(proof: &ZkMintProof, packet: &PoEPacket) -> [u8; 32] {
+        let mut proof_preimage = proof.vk_hash.to_vec();
+        for input in &proof.public_inputs {
+            proof_preimage.extend_from_slice(input);
+        }
+        proof_preimage.extend_from_slice(&proof.proof);
+        
+        let mut preimage = sha256(&proof_preimage).to_vec();
+        preimage.extend_from_slice(&packet.device_id);
+        preimage.extend_from_slice(&packet.energy_wh.to_be_bytes());
+        preimage.extend_from_slice(&packet.timestamp.to_be_bytes());
+        sha256(&preimage)
+    }
+    
+    /// Signed packet advancing the counter to `cumulative` with its proof
+    fn proven(params: &Parameters<Bls12>, cumulative: u128) -> (PoEPacket, ZkMintProof) {
+        let packet = signed(PoEPacket { cumulative_energy: cumulative, ..packet(1_000_000) });
+        let proof = prove(params, public_inputs(&packet, classification_digest(REGION, 0)));
+        (packet, proof)
+    }
+    
+    #[test]
+    fn retry_of_a_rejected_mint_hits_the_proof_cache() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        link_vault(&mut minter, 0);
+        
+        let (first, first_proof) = proven(&params, 1_000_000);
+        let (second, second_proof) = proven(&params, 2_000_000);
+        testing::set_caller(PROSUMER);
+        for (packet, proof) in [(&first, &first_proof), (&second, &second_proof)] {
+            assert_eq!(
+                minter.mint_with_poe(packet.clone(), proof.clone(), [8u8; 32]),
+                Err("Mint exceeds eligible backing".into())
+            );
+        }
+        let (first_key, second_key) = (cache_key(&first_proof, &first), cache_key(&second_proof, &second));
+        assert_eq!(minter.proof_cache_order, vec![first_key, second_key]);
+        
+        // Backing arrives; the retry is served from the cache and becomes most recent
+        testing::mock_view::<(), u64>(VAULT, "mint_capacity", |_| Ok(10));
+        assert_eq!(minter.mint_with_poe(first, first_proof, [8u8; 32]), Ok(1));
+        assert_eq!(minter.proof_cache_order, vec![second_key, first_key]);
+    }
+    
+    #[test]
+    fn proof_cache_evicts_the_least_recently_verified() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_proof_cache_capacity(2).unwrap();
+        
+        let mut keys = Vec::new();
+        testing::set_caller(PROSUMER);
+        for cumulative in [1_000_000, 2_000_000, 3_000_000] {
+            let (packet, proof) = proven(&params, cumulative);
+            keys.push(cache_key(&proof, &packet));
+            minter.mint_with_poe(packet, proof, [8u8; 32]).unwrap();
+        }
+        
+        assert_eq!(minter.proof_cache_order, keys[1..].to_vec());
+        assert!(!minter.verified_proof_cache.contains_key(&keys[0]));
+        assert!(minter.verified_proof_cache.contains_key(&keys[2]));
+        
+        // Shrinking evicts straight away
+        testing::set_caller(ADMIN);
+        minter.set_proof_cache_capacity(1).unwrap();
+        assert_eq!(minter.proof_cache_order, vec![keys[2]]);
+    }
+    
+    #[test]
+    fn replayed_attestation_is_rejected() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_oracle_role(oracle().public.to_bytes(), OracleRole::AttestOnly).unwrap();
+        
+        let (packet, proof) = proven(&params, 1_000_000);
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet.clone(), proof.clone(), [8u8; 32]), Ok(0));
+        assert_eq!(minter.device_attested_energy.get(&DEVICE), Some(1_000_000));
+        
+        assert_eq!(
+            minter.mint_with_poe(packet.clone(), proof, [8u8; 32]),
+            Err("Proof already used".into())
+        );
+        
+        // A fresh proof of the same packet doesn't advance the counter either
+        let reproved = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        assert_eq!(
+            minter.mint_with_poe(packet, reproved, [8u8; 32]),
+            Err("Stale cumulative energy".into())
+        );
+        assert_eq!(minter.device_attested_energy.get(&DEVICE), Some(1_000_000));
+        assert_eq!(minter.total_minted, 0);
+    }
+    
+    #[test]
+    fn batch_counts_attestations_apart_from_mints() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_oracle_role(oracle().public.to_bytes(), OracleRole::AttestOnly).unwrap();
+        
+        let (first, first_proof) = proven(&params, 1_000_000);
+        let items = vec![
+            (first.clone(), first_proof.clone(), [8u8; 32], None),
+            (first, first_proof, [8u8; 32], None),
+        ];
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_batch(items, 2),
+            Ok(vec![Ok(0), Err("Proof already used".into())])
+        );
+        
+        let batches = testing::emitted_events::<BatchMintedEvent>("BatchMinted");
+        let batch = batches.last().unwrap();
+        assert_eq!((batch.succeeded, batch.attested, batch.failed), (0, 1, 1));
+    }
+    
+    #[test]
+    fn valid_group_signature_mints() {
+        let params = params();
+        let mut minter = minter(&params);
+        let group = keypair(7); // Stands in for the aggregated FROST key
+        minter.set_oracle_group_key(group.public.to_bytes(), 2).unwrap();
+        
+        let packet = packet(2_000_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let signature = group.sign(&group_signing_bytes(&packet, 2)).to_bytes();
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_threshold_signature(packet, signature, proof, [8u8; 32], None), Ok(2));
+    }
+    
+    #[test]
+    fn wrong_key_or_threshold_group_signature_is_rejected() {
+        let params = params();
+        let mut minter = minter(&params);
+        let group = keypair(7);
+        minter.set_oracle_group_key(group.public.to_bytes(), 2).unwrap();
+        
+        let packet = packet(2_000_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let forged = [
+            keypair(8).sign(&group_signing_bytes(&packet, 2)).to_bytes(),
+            // Shares generated for a lower threshold than the one registered
+            group.sign(&group_signing_bytes(&packet, 1)).to_bytes(),
+            // A plain packet signature isn't bound to any threshold
+            group.sign(&packet_signing_bytes(&packet)).to_bytes(),
+        ];
+        
+        testing::set_caller(PROSUMER);
+        for signature in forged {
+            assert_eq!(
+                minter.mint_with_threshold_signature(packet.clone(), signature, proof.clone(), [8u8; 32], None),
+                Err("Invalid group signature".into())
+            );
+        }
+    }
+    
+    #[test]
+    fn group_threshold_may_not_undercut_the_oracle_quorum() {
+        let params = params();
+        let mut minter = minter(&params);
+        let group = keypair(7);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.add_oracle(keypair(6).public.to_bytes()).unwrap();
+        minter.set_oracle_group_key(group.public.to_bytes(), 1).unwrap();
+        minter.set_oracle_threshold(2).unwrap();
+        
+        assert_eq!(
+            minter.set_oracle_group_key(group.public.to_bytes(), 1),
+            Err("Group threshold below oracle quorum".into())
+        );
+        
+        // A group registered before the quorum was raised can no longer mint
+        let packet = packet(2_000_000);
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let signature = group.sign(&group_signing_bytes(&packet, 1)).to_bytes();
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_threshold_signature(packet, signature, proof, [8u8; 32], None),
+            Err("Group threshold below oracle quorum".into())
+        );
+    }
+    
+    #[test]
+    fn proof_for_one_device_cannot_mint_for_another() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        let other_device = [2u8; 32];
+        testing::set_caller(PROSUMER);
+        minter.register_device(other_device, REGION, EnergyUnit::Wh).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(other_device, 1_000_000_000, 1).unwrap();
+        
+        // Proven for `DEVICE`, then claimed for another certified device
+        let packet = signed(packet(2_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let retargeted = signed(PoEPacket { device_id: other_device, ..packet.clone() });
+        assert_eq!(
+            minter.verify_proof_only(retargeted.clone(), proof.clone()),
+            Err(MinterError::InvalidProof("Proof doesn't match device".into()))
+        );
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe(retargeted, proof.clone(), [8u8; 32]),
+            Err("Proof doesn't match device".into())
+        );
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(2));
+    }
+    
+    #[test]
+    fn oracle_is_capped_per_window_but_others_still_mint() {
+        let params = params();
+        let mut minter = minter(&params);
+        let second = keypair(6);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.add_oracle(second.public.to_bytes()).unwrap();
+        minter.set_oracle_rate_limit(2, 3_600).unwrap();
+        
+        testing::set_caller(PROSUMER);
+        for cumulative in [1_000_000, 2_000_000] {
+            let (packet, proof) = proven(&params, cumulative);
+            assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(1));
+        }
+        
+        let (over, over_proof) = proven(&params, 3_000_000);
+        assert_eq!(
+            minter.mint_with_poe(over.clone(), over_proof.clone(), [8u8; 32]),
+            Err("Oracle rate limit".into())
+        );
+        
+        // The same reading signed by an oracle with room left in its window
+        let mut relayed = PoEPacket { oracle_id: second.public.to_bytes(), ..over };
+        relayed.signature = second.sign(&packet_signing_bytes(&relayed)).to_bytes();
+        assert_eq!(minter.mint_with_poe(relayed, over_proof, [8u8; 32]), Ok(1));
+        assert_eq!(minter.total_minted, 3);
+    }
+    
+    /// Burn proof under the burn circuit's key; only accepted with pairing checks skipped
+    fn burn_proof() -> ZkMintProof {
+        let mut vk_hash = [0u8; 32];
+        for chunk in vk_hash.chunks_mut(8) {
+            chunk.copy_from_slice(&[0xfe, 0xdc, 0xba, 0x09, 0x87, 0x65, 0x43, 0x21]);
+        }
+        ZkMintProof { proof: vec![1], public_inputs: Vec::new(), vk_hash }
+    }
+    
+    #[test]
+    fn burns_may_not_exceed_total_minted() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        // 3 minted, 2 of them to the prosumer
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        minter.mint_with_poe(packet, proof, [8u8; 32]).unwrap();
+        minter.verification_mode = VerificationMode::TrustedSkip;
+        
+        assert_eq!(minter.burn_for_assets(2, "bitcoin".into(), "bc1q".into(), burn_proof()), Ok(()));
+        assert_eq!(minter.total_burned, 2);
+        
+        // A balance the mint accounting never saw, as a bug elsewhere might leave
+        minter.balances.insert(PROSUMER.into(), 5);
+        assert_eq!(
+            minter.burn_for_assets(2, "bitcoin".into(), "bc1q".into(), burn_proof()),
+            Err("Burn exceeds minted".into())
+        );
+        assert_eq!(minter.total_burned, 2);
+    }
+    
+    #[test]
+    fn equal_energy_mints_by_each_device_region() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        // A second device in a grid where energy is worth twice as much
+        let (other_device, other_region) = ([2u8; 32], REGION + 1);
+        testing::set_caller(PROSUMER);
+        minter.register_device(other_device, other_region, EnergyUnit::Wh).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(other_device, 1_000_000_000, 1).unwrap();
+        minter.set_region_wh_per_token(other_region, WH_PER_TOKEN / 2).unwrap();
+        
+        let other = signed(PoEPacket { device_id: other_device, ..packet(2_000_000) });
+        let packet = signed(packet(2_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        let other_proof = prove_for(&params, other_device, public_inputs(&other, classification_digest(other_region, 0)));
+        
+        // `REGION` has no ratio of its own and falls back to the global one
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(2));
+        assert_eq!(minter.mint_with_poe(other, other_proof, [9u8; 32]), Ok(4));
+    }
+    
+    #[test]
+    fn bridged_burn_emits_a_complete_instruction() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_bridge_fee("bitcoin".into(), 20).unwrap();
+        
+        let packet = signed(packet(3_000_000));
+        let proof = prove(&params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        minter.mint_with_poe(packet, proof, [8u8; 32]).unwrap();
+        minter.verification_mode = VerificationMode::TrustedSkip;
+        
+        minter.burn_for_assets(2, "bitcoin".into(), "bc1q".into(), burn_proof()).unwrap();
+        
+        let bridged = testing::emitted_events::<AssetsBridgedEvent>("AssetsBridged");
+        assert_eq!(bridged.len(), 1);
+        assert_eq!(
+            bridged[0].instruction,
+            BridgeInstruction {
+                version: BRIDGE_INSTRUCTION_VERSION,
+                burn_id: 0,
+                amount: 2,
+                usd_value: 2 * TOKEN_PRICE_USD,
+                bridge_fee_usd: 20,
+                net_usd_value: 2 * TOKEN_PRICE_USD - 20,
+                dest_chain: "bitcoin".into(),
+                dest_address: "bc1q".into(),
+                deadline: NOW_SECS + DEFAULT_BRIDGE_DEADLINE_SECS,
+            }
+        );
+    }
+    
+    #[test]
+    fn zero_burn_is_rejected_before_anything_is_bridged() {
+        let params = params();
+        let mut minter = minter(&params);
+        
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.burn_for_assets(0, "bitcoin".into(), "bc1q".into(), burn_proof()),
+            Err("Zero burn amount".into())
+        );
+        assert!(testing::emitted_events::<AssetsBridgedEvent>("AssetsBridged").is_empty());
+        assert_eq!(minter.burn_count, 0);
+    }
+    
+    #[test]
+    fn admin_actions_are_logged_with_caller_time_and_params() {
+        let params = params();
+        let mut minter = minter(&params);
+        let (logged, head) = (minter.admin_action_log.len() as u64, minter.admin_log_head);
+        
+        testing::set_block_timestamp(NOW_SECS + 60);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.set_oracle_rate_limit(5, 600).unwrap();
+        
+        // Callers that aren't admin leave no trace
+        testing::set_caller(PROSUMER);
+        assert!(minter.set_oracle_rate_limit(1, 600).is_err());
+        
+        let actions = minter.admin_actions(logged, 10);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(
+            actions[0],
+            AdminAction {
+                action: "add_oracle".into(),
+                caller: ADMIN.into(),
+                timestamp: NOW_SECS + 60,
+                params_hash: sha256(&oracle().public.to_bytes().encode()),
+                prev_hash: head,
+            }
+        );
+        assert_eq!(actions[1].action, "set_oracle_rate_limit");
+        assert_eq!(actions[1].params_hash, sha256(&(5u32, 600u64).encode()));
+        
+        // Each entry chains to the one before it
+        assert_eq!(actions[1].prev_hash, sha256(&actions[0].encode()));
+        assert_eq!(minter.admin_log_head, sha256(&actions[1].encode()));
+        
+        let events = testing::emitted_events::<AdminActionEvent>("AdminAction");
+        assert_eq!(events.last().map(|event| &event.entry), Some(&actions[1]));
+    }
+    
+    /// A circuit with one public input, standing in for another circuit's parameters
+    struct OneInputCircuit;
+    
+    impl Circuit<Scalar> for OneInputCircuit {
+        fn synthesize<CS: ConstraintSystem<Scalar>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+            let x = cs.alloc_input(|| "x", || Ok(Scalar::one()))?;
+            cs.enforce(|| "x * 1 = x", |lc| lc + x, |lc| lc + CS::one(), |lc| lc + x);
+            Ok(())
+        }
+    }
+    
+    /// Write `params` out as a parameter file and load it back
+    fn reload(params: &Parameters<Bls12>, name: &str) -> Result<Parameters<Bls12>, String> {
+        let path = std::env::temp_dir().join(format!("poe-params-{}-{}.bin", name, std::process::id()));
+        params.write(File::create(&path).unwrap()).unwrap();
+        let loaded = load_params(&path);
+        let _ = std::fs::remove_file(&path);
+        loaded
+    }
+    
+    #[test]
+    fn matching_parameter_file_loads() {
+        let params = params();
+        
+        assert_eq!(validate_params(&params), Ok(()));
+        assert!(reload(&params, "matching").is_ok());
+    }
+    
+    #[test]
+    fn parameter_file_for_another_circuit_is_rejected() {
+        let foreign = generate_random_parameters::<Bls12, _, _>(OneInputCircuit, &mut rand::thread_rng()).unwrap();
+        let mismatch = format!("Parameter mismatch: expected {} public inputs, found 1", POE_PUBLIC_INPUT_COUNT);
+        
+        assert_eq!(validate_params(&foreign), Err(mismatch.clone()));
+        assert_eq!(reload(&foreign, "foreign").err(), Some(mismatch));
+    }
+    
+    /// Mint `tokens` for the prosumer, leaving 15% of them to the treasury
+    fn mint_tokens(params: &Parameters<Bls12>, minter: &mut PoEzkBTCMinter, tokens: u64) {
+        let packet = signed(packet(tokens * WH_PER_TOKEN));
+        let proof = prove(params, public_inputs(&packet, classification_digest(REGION, 0)));
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(tokens));
+    }
+    
+    #[test]
+    fn single_treasury_takes_the_whole_protocol_share() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        mint_tokens(&params, &mut minter, 40);
+        assert_eq!(minter.balance_of(PROSUMER.into()), 34);
+        assert_eq!(minter.balance_of("treasury".into()), 6);
+    }
+    
+    #[test]
+    fn weighted_treasury_split_hands_rounding_dust_to_the_first_recipient() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        assert_eq!(
+            minter.set_treasury_splits(vec![("dao".into(), 5_000), ("grants".into(), 3_000)]),
+            Err("Treasury splits must sum to 10000 bps".into())
+        );
+        minter.set_treasury_splits(vec![
+            ("dao".into(), 5_000),
+            ("grants".into(), 3_000),
+            ("insurance".into(), 2_000),
+        ]).unwrap();
+        
+        // 6 protocol tokens split 3 / 1.8 / 1.2, the floored-off token going to "dao"
+        mint_tokens(&params, &mut minter, 40);
+        let shares: Vec<u64> = ["dao", "grants", "insurance"]
+            .iter()
+            .map(|recipient| minter.balance_of(recipient.to_string()))
+            .collect();
+        assert_eq!(shares, vec![4, 1, 1]);
+        assert_eq!(minter.balance_of("treasury".into()), 0);
+    }
+    
+    #[test]
+    fn emergency_oracle_mints_at_once_and_lapses_unless_ratified() {
+        let params = params();
+        let mut minter = minter(&params);
+        let ratified = keypair(6);
+        minter.emergency_add_oracle(oracle().public.to_bytes()).unwrap();
+        minter.emergency_add_oracle(ratified.public.to_bytes()).unwrap();
+        
+        let added = testing::emitted_events::<EmergencyOracleAddedEvent>("EmergencyOracleAdded");
+        assert_eq!(added.len(), 2);
+        assert_eq!(added[0].added_by, ADMIN);
+        assert_eq!(added[0].expires_at, NOW_SECS + DEFAULT_EMERGENCY_ORACLE_TTL);
+        
+        // Authorized without waiting on governance
+        let (packet, proof) = proven(&params, 1_000_000);
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(1));
+        
+        testing::set_caller(ADMIN);
+        minter.ratify_emergency_oracle(ratified.public.to_bytes()).unwrap();
+        
+        testing::set_block_timestamp(NOW_SECS + DEFAULT_EMERGENCY_ORACLE_TTL);
+        let (packet, proof) = proven(&params, 2_000_000);
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Err("Oracle not authorized".into()));
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(
+            minter.ratify_emergency_oracle(oracle().public.to_bytes()),
+            Err("Emergency oracle expired".into())
+        );
+        assert_eq!(
+            minter.add_oracle(ratified.public.to_bytes()),
+            Err("Oracle already registered".into())
+        );
+        assert_eq!(minter.total_minted, 1);
+    }
+    
+    /// Certified device of `PROSUMER` whose meter reports in `unit`
+    fn certify_in(minter: &mut PoEzkBTCMinter, device_id: [u8; 32], unit: EnergyUnit) {
+        testing::set_caller(PROSUMER);
+        minter.register_device(device_id, REGION, unit).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(device_id, 1_000_000_000, 1).unwrap();
+    }
+    
+    #[test]
+    fn kwh_and_mj_readings_are_normalized_to_wh() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        let kwh_meter = [2u8; 32];
+        let mj_meter = [3u8; 32];
+        certify_in(&mut minter, kwh_meter, EnergyUnit::KWh);
+        certify_in(&mut minter, mj_meter, EnergyUnit::MJ);
+        
+        // 2_000 kWh and 7_200 MJ are both 2 MWh
+        testing::set_caller(PROSUMER);
+        for (device_id, reading) in [(kwh_meter, 2_000), (mj_meter, 7_200)] {
+            let packet = signed(PoEPacket { device_id, ..packet(reading) });
+            let proof = prove_for(&params, device_id, public_inputs(&packet, classification_digest(REGION, 0)));
+            assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(2));
+            assert_eq!(minter.device_energy_total.get(&device_id), Some(2 * WH_PER_TOKEN as u128));
+        }
+        assert_eq!(minter.total_minted, 4);
+    }
+    
+    /// Minter on `network` that skips the pairing check, with `DEVICE` certified
+    fn skipping_minter(network: Network) -> PoEzkBTCMinter {
+        testing::reset();
+        testing::set_block_timestamp(NOW_SECS);
+        testing::set_block_height(ANCHOR_HEIGHT);
+        testing::set_block_hash(ANCHOR_HEIGHT, ANCHOR_HASH);
+        
+        testing::set_caller(ADMIN);
+        let mut minter = PoEzkBTCMinter::new(ADMIN.into(), "treasury".into(), network, VerificationMode::TrustedSkip);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        certify_in(&mut minter, DEVICE, EnergyUnit::Wh);
+        minter
+    }
+    
+    #[test]
+    fn skip_mode_mints_unproven_packets_on_devnet_but_still_checks_replay() {
+        let mut minter = skipping_minter(Network::Devnet);
+        
+        // No verifying key is registered and the proof bytes are junk
+        let reading = signed(packet(2_000_000));
+        let unproven = ZkMintProof {
+            proof: vec![0u8; 128],
+            public_inputs: public_inputs(&reading, classification_digest(REGION, 0)).to_public_inputs(),
+            vk_hash: VK_HASH,
+        };
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(reading.clone(), unproven.clone(), [8u8; 32]), Ok(2));
+        assert_eq!(
+            minter.mint_with_poe(reading, unproven.clone(), [8u8; 32]),
+            Err("Proof already used".into())
+        );
+        
+        let retargeted = signed(PoEPacket { device_id: [2u8; 32], ..packet(3_000_000) });
+        assert_eq!(
+            minter.mint_with_poe(retargeted, unproven, [8u8; 32]),
+            Err("Device not certified".into())
+        );
+    }
+    
+    #[test]
+    #[should_panic(expected = "Proof verification cannot be skipped on mainnet")]
+    fn skip_mode_is_refused_on_mainnet() {
+        skipping_minter(Network::Mainnet);
+    }
+    
+    #[test]
+    fn cumulative_counter_past_the_device_lifetime_is_rejected() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        let small_array = [2u8; 32];
+        testing::set_caller(PROSUMER);
+        minter.register_device(small_array, REGION, EnergyUnit::Wh).unwrap();
+        testing::set_caller(ADMIN);
+        minter.certify_device(small_array, 5_000_000, 1).unwrap();
+        
+        testing::set_caller(PROSUMER);
+        for (cumulative, expected) in [
+            (9_000_000, Err("Cumulative energy exceeds device lifetime".into())),
+            (5_000_000, Ok(2)),
+        ] {
+            let reading = signed(PoEPacket { device_id: small_array, cumulative_energy: cumulative, ..packet(2_000_000) });
+            let proof = prove_for(&params, small_array, public_inputs(&reading, classification_digest(REGION, 0)));
+            assert_eq!(minter.mint_with_poe(reading, proof, [8u8; 32]), expected);
+        }
+        assert_eq!(minter.total_minted, 2);
+    }
+    
+    #[test]
+    fn oracle_key_can_only_be_registered_once() {
+        let params = params();
+        let mut minter = minter(&params);
+        let key = oracle().public.to_bytes();
+        minter.add_oracle(key).unwrap();
+        
+        // The oracle id is the public key, so a second entry would be the same key
+        assert_eq!(minter.add_oracle(key), Err("Oracle already registered".into()));
+        assert_eq!(minter.emergency_add_oracle(key), Err("Oracle already authorized".into()));
+        assert_eq!(minter.export_oracles().len(), 1);
+    }
+    
+    #[test]
+    fn mint_log_pages_through_a_time_range() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        // One token a minute, each proven at its own block time
+        testing::set_caller(PROSUMER);
+        for minute in 0..5u64 {
+            let at = NOW_SECS + minute * 60;
+            testing::set_block_timestamp(at);
+            let reading = signed(PoEPacket {
+                timestamp: at * 1_000 - 60_000,
+                cumulative_energy: (minute as u128 + 1) * 1_000_000,
+                ..packet(1_000_000)
+            });
+            let public = PoEPublicInputs {
+                current_time: at * 1_000,
+                ..public_inputs(&reading, classification_digest(REGION, 0))
+            };
+            assert_eq!(minter.mint_with_poe(reading, prove(&params, public), [8u8; 32]), Ok(1));
+        }
+        
+        let (start, end) = (NOW_SECS + 60, NOW_SECS + 180);
+        let (page, cursor) = minter.mints_in_range(start, end, None, 2);
+        assert_eq!(page.iter().map(|record| record.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(page[0].timestamp, start);
+        assert_eq!((page[0].wallet.as_str(), page[0].energy_wh, page[0].tokens), (PROSUMER, 1_000_000, 1));
+        assert_eq!(cursor, Some(3));
+        
+        let (rest, cursor) = minter.mints_in_range(start, end, cursor, 2);
+        assert_eq!(rest.iter().map(|record| record.timestamp).collect::<Vec<_>>(), vec![end]);
+        assert_eq!(cursor, None);
+    }
+    
+    #[test]
+    fn blocked_firmware_stops_mints_until_recertified() {
+        let params = params();
+        let mut minter = minter(&params);
+        minter.add_oracle(oracle().public.to_bytes()).unwrap();
+        
+        // `DEVICE` was certified on firmware 1
+        let (packet, proof) = proven(&params, 1_000_000);
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(1));
+        
+        testing::set_caller(ADMIN);
+        minter.set_firmware_blocked(1, true).unwrap();
+        let (packet, proof) = proven(&params, 2_000_000);
+        testing::set_caller(PROSUMER);
+        assert_eq!(
+            minter.mint_with_poe(packet.clone(), proof.clone(), [8u8; 32]),
+            Err("Device firmware blocked".into())
+        );
+        
+        testing::set_caller(ADMIN);
+        minter.recertify_firmware(DEVICE, 2).unwrap();
+        testing::set_caller(PROSUMER);
+        assert_eq!(minter.mint_with_poe(packet, proof, [8u8; 32]), Ok(1));
+    }
+}
@@ -0,0 +1,233 @@
+//! Event payloads emitted by the vault
+//! Every `emit_event` passes one of these structs; bump `EVENT_SCHEMA_VERSION`
+//! whenever a field is added, removed or reordered.
+
+use charms_sdk::prelude::*;
+
+use crate::grail_vault::ReserveSnapshot;
+
+/// Layout version carried by every event payload
+pub const EVENT_SCHEMA_VERSION: u16 = 2;
+
+/// `BackingThrottled`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BackingThrottledEvent {
+    pub total_backing_usd: u128,
+    pub mint_eligible_usd: u128,
+    pub schema_version: u16,
+}
+
+/// `BackingReversed`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BackingReversedEvent {
+    pub txid: [u8; 32],
+    pub asset_key: String,
+    pub amount: u128,
+    pub usd_value: u64,
+    pub schema_version: u16,
+}
+
+/// `BackingWithdrawn`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BackingWithdrawnEvent {
+    pub chain: String,
+    pub token_address: String,
+    pub amount: u128,
+    pub usd_value: u64,
+    pub recipient: String,
+    pub schema_version: u16,
+}
+
+/// `RedemptionRequested`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionRequestedEvent {
+    pub recipient: String,
+    pub amount: u64,
+    pub usd_value: u64,
+    pub schema_version: u16,
+}
+
+/// `RedemptionEnqueued`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionEnqueuedEvent {
+    pub id: u64,
+    pub dest_chain: String,
+    pub recipient: String,
+    pub amount: u64,
+    pub usd_value: u64,
+    pub queue_depth: u32,
+    pub schema_version: u16,
+}
+
+/// `RedemptionDequeued`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionDequeuedEvent {
+    pub id: u64,
+    pub recipient: String,
+    pub amount: u64,
+    pub queue_depth: u32,
+    pub schema_version: u16,
+}
+
+/// `RedemptionSettled`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionSettledEvent {
+    pub recipient: String,
+    pub amount: u64,
+    pub usd_value: u64,
+    pub schema_version: u16,
+}
+
+/// `RedemptionReassigned`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionReassignedEvent {
+    pub id: u64,
+    pub old_recipient: String,
+    pub new_recipient: String,
+    pub schema_version: u16,
+}
+
+/// `RedemptionCancelled`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionCancelledEvent {
+    pub id: u64,
+    pub owner: String,
+    pub amount: u64,
+    pub usd_value: u64,
+    pub schema_version: u16,
+}
+
+/// `RedemptionExpired`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RedemptionExpiredEvent {
+    pub recipient: String,
+    pub amount: u64,
+    pub enqueued_at: u64,
+    /// Whether the tokens went back to the holder
+    pub refunded: bool,
+    pub schema_version: u16,
+}
+
+/// `QueueDepthChanged`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct QueueDepthChangedEvent {
+    pub queue_depth: u32,
+    pub schema_version: u16,
+}
+
+/// `ChainPayout`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ChainPayoutEvent {
+    pub chain: String,
+    /// (recipient, token_address, amount, usd)
+    pub items: Vec<(String, String, u128, u64)>,
+    pub total_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `DirectRelease`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct DirectReleaseEvent {
+    pub recipient: String,
+    pub chain: String,
+    pub amount: u64,
+    /// (token_address, amount, usd)
+    pub released: Vec<(String, u128, u64)>,
+    pub usd_value: u64,
+    pub schema_version: u16,
+}
+
+/// `Rebalanced`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RebalancedEvent {
+    pub sold_key: String,
+    pub sold_amount: u128,
+    pub sold_usd: u64,
+    pub bought_key: String,
+    pub bought_amount: u128,
+    pub bought_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `ReservesAttested`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ReservesAttestedEvent {
+    pub snapshot: ReserveSnapshot,
+    pub signature: [u8; 64],
+    pub attester: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `ReserveAttesterUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ReserveAttesterUpdatedEvent {
+    pub attester: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `UtxoVerifierUpdated` / `MinterUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ContractLinkUpdatedEvent {
+    pub address: String,
+    pub schema_version: u16,
+}
+
+/// `PriceUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct PriceUpdatedEvent {
+    pub price_per_token_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `HaircutUpdated` / `TargetWeightUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct AssetBpsUpdatedEvent {
+    pub asset_key: String,
+    pub bps: u16,
+    pub schema_version: u16,
+}
+
+/// `AssetPaused` / `AssetUnpaused`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct AssetPauseEvent {
+    pub asset_key: String,
+    pub schema_version: u16,
+}
+
+/// `BackingGrowthCapUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BackingGrowthCapUpdatedEvent {
+    pub bps: u16,
+    pub window: u64,
+    pub schema_version: u16,
+}
+
+/// `MinCollateralUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct MinCollateralUpdatedEvent {
+    pub bps: u64,
+    pub schema_version: u16,
+}
+
+/// `MinBackingUpdated`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct MinBackingUpdatedEvent {
+    pub min_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `AdminTransferStarted`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct AdminTransferStartedEvent {
+    pub admin: String,
+    pub pending_admin: String,
+    pub schema_version: u16,
+}
+
+/// `AdminTransferCompleted`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct AdminTransferCompletedEvent {
+    pub previous_admin: String,
+    pub admin: String,
+    pub schema_version: u16,
+}
@@ -2,6 +2,55 @@
 //! Handles multi-asset backing for zkBTC-E
 
 use charms_sdk::prelude::*;
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use std::collections::BTreeMap;
+use utxo_verifier::utxo_verifier::UTXOPayment;
+
+pub mod events;
+pub use events::*;
+
+/// Basis-point denominator (100%)
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Default window over which backing growth is capped (seconds)
+pub const DEFAULT_BACKING_GROWTH_WINDOW: u64 = 86_400;
+
+/// Initial USD value redeemed per zkBTC-E
+pub const DEFAULT_PRICE_PER_TOKEN_USD: u64 = 70;
+
+/// Most sibling vaults `aggregate_reserves` reads
+pub const MAX_SIBLING_VAULTS: usize = 32;
+
+/// Bytes a holder signs to let someone else queue a redemption of their tokens
+/// at `vault`, paid out on `dest_chain`
+pub fn redemption_auth_bytes(
+    vault: &str,
+    owner: &str,
+    amount: u64,
+    dest_chain: &str,
+    recipient: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    (b"grail-vault:redeem".to_vec(), vault, owner, amount, dest_chain, recipient, nonce).encode()
+}
+
+/// Bytes a holder signs to redirect their queued redemption `id` at `vault` to
+/// `new_recipient`
+pub fn reassignment_auth_bytes(
+    vault: &str,
+    owner: &str,
+    id: u64,
+    old_recipient: &str,
+    new_recipient: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    (b"grail-vault:reassign".to_vec(), vault, owner, id, old_recipient, new_recipient, nonce).encode()
+}
+
+/// Bytes a holder signs to withdraw their queued redemption `id` at `vault`
+pub fn cancellation_auth_bytes(vault: &str, owner: &str, id: u64, nonce: u64) -> Vec<u8> {
+    (b"grail-vault:cancel".to_vec(), vault, owner, id, nonce).encode()
+}
 
 #[wasm::contract]
 pub mod grail_vault {
@@ -15,6 +64,66 @@ pub mod grail_vault {
         pub usd_value: u64,
     }
     
+    /// A queued redemption awaiting settlement
+    #[derive(Debug, Clone, Encode, Decode)]
+    pub struct RedemptionRequest {
+        /// Sequential id, stable while queue positions shift
+        pub id: u64,
+        /// Holder whose zkBTC-E was burned for this request
+        pub owner: String,
+        /// Chain the backing is paid out on; `recipient` is an address there
+        pub dest_chain: String,
+        pub recipient: String,
+        pub amount: u64,
+        /// Backing USD reserved for this request
+        pub usd_value: u64,
+        /// Block timestamp (seconds) when the request was queued
+        pub enqueued_at: u64,
+    }
+    
+    /// Reserves and supply at a point in time, as signed by the reserve attester
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    pub struct ReserveSnapshot {
+        pub total_backing_usd: u128,
+        /// Backing after per-asset haircuts, as solvency counts it
+        pub effective_backing_usd: u128,
+        pub reserved_backing_usd: u128,
+        pub zkbtce_supply: u64,
+        /// Block timestamp (seconds) the snapshot was read at
+        pub taken_at: u64,
+    }
+    
+    /// Reserves summed across this vault and its registered siblings
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    pub struct AggregateReserves {
+        pub total_backing_usd: u128,
+        pub effective_backing_usd: u128,
+        pub reserved_backing_usd: u128,
+        pub zkbtce_supply: u64,
+        /// Combined unreserved haircut backing over combined supply, in bps
+        pub collateral_ratio_bps: u64,
+        /// Vaults whose reserves are included, this one among them
+        pub vaults_counted: u32,
+        /// Siblings that could not be read; the totals exclude them
+        pub unreachable: Vec<String>,
+    }
+    
+    impl AggregateReserves {
+        /// Whether some sibling was left out of the totals
+        pub fn is_partial(&self) -> bool {
+            !self.unreachable.is_empty()
+        }
+    }
+    
+    /// What happens to the tokens of a redemption that expires unfulfilled
+    #[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+    pub enum ExpiryPolicy {
+        /// Return the tokens to circulating supply
+        Refund,
+        /// Keep the tokens burned
+        Forfeit,
+    }
+    
     #[contract(state)]
     pub struct GrailVault {
         #[state]
@@ -23,6 +132,9 @@ pub mod grail_vault {
         #[state]
         pub total_backing_usd: u128,
         
+        #[state]
+        pub reserved_backing_usd: u128, // promised to queued redemptions
+        
         #[state]
         pub backing_assets: Map<String, BackingAsset>, // chain+address -> asset
         
@@ -30,23 +142,116 @@ pub mod grail_vault {
         pub zkbtce_supply: u64,
         
         #[state]
-        pub redemption_queue: Vec<(String, u64)>, // (requester, amount)
+        pub redemption_queue: Vec<RedemptionRequest>,
+        
+        #[state]
+        pub haircut_bps: Map<String, u16>, // chain+address -> discount applied in solvency
+        
+        #[state]
+        pub min_backing_usd: u64, // smallest deposit add_backing will credit
+        
+        #[state]
+        pub queue_entry_ttl: u64, // seconds a redemption may wait before expiring (0 = never)
+        
+        #[state]
+        pub expiry_policy: ExpiryPolicy,
+        
+        #[state]
+        pub utxo_verifier: String, // UTXOVerifier contract backing deposits are checked against
+        
+        #[state]
+        pub credited_payments: Map<[u8; 32], bool>, // txids already credited as backing
+        
+        #[state]
+        pub payment_backing: Map<[u8; 32], (String, u128, u64)>, // txid -> (asset key, amount, usd) it credited
+        
+        #[state]
+        pub price_per_token_usd: u64, // USD value redeemed per zkBTC-E
+        
+        #[state]
+        pub minter: String, // PoE minter holding zkBTC-E balances
+        
+        #[state]
+        pub target_weights: Map<String, u16>, // chain+address -> target share of backing in bps
+        
+        #[state]
+        pub reserve_attester: Option<[u8; 32]>, // Ed25519 key that signs reserve snapshots
+        
+        #[state]
+        pub pending_admin: Option<String>, // proposed admin awaiting accept_admin
+        
+        #[state]
+        pub backing_growth_cap_bps: u16, // max growth of mint-eligible backing per window (0 = uncapped)
+        
+        #[state]
+        pub backing_growth_window: u64, // seconds
+        
+        #[state]
+        pub growth_window_start: u64,
+        
+        #[state]
+        pub growth_window_base: u128, // mint-eligible backing when the window started
+        
+        #[state]
+        pub min_collateral_bps: u64, // collateral ratio redemptions may not push below (0 = off)
+        
+        #[state]
+        pub redemption_keys: Map<String, [u8; 32]>, // holder -> Ed25519 key authorizing delegated redemptions
+        
+        #[state]
+        pub redemption_nonces: Map<String, u64>, // holder -> next delegated redemption nonce
+        
+        #[state]
+        pub paused_assets: Map<String, bool>, // chain+address -> deposits and redemptions halted
+        
+        #[state]
+        pub next_redemption_id: u64,
+        
+        #[state]
+        pub sibling_vaults: Vec<String>, // regional vaults counted in aggregate reserves
     }
     
     #[contract(impl)]
     impl GrailVault {
         #[constructor]
         pub fn new(admin: String) -> Self {
+            // An empty admin would leave every admin-gated message unusable
+            assert!(!admin.is_empty(), "Admin must not be empty");
+            
             Self {
                 admin,
                 total_backing_usd: 0,
+                reserved_backing_usd: 0,
                 backing_assets: Map::new(),
                 zkbtce_supply: 0,
                 redemption_queue: Vec::new(),
+                haircut_bps: Map::new(),
+                min_backing_usd: 0,
+                queue_entry_ttl: 0,
+                expiry_policy: ExpiryPolicy::Refund,
+                utxo_verifier: String::new(),
+                credited_payments: Map::new(),
+                payment_backing: Map::new(),
+                price_per_token_usd: DEFAULT_PRICE_PER_TOKEN_USD,
+                minter: String::new(),
+                target_weights: Map::new(),
+                reserve_attester: None,
+                pending_admin: None,
+                backing_growth_cap_bps: 0,
+                backing_growth_window: DEFAULT_BACKING_GROWTH_WINDOW,
+                growth_window_start: 0,
+                growth_window_base: 0,
+                min_collateral_bps: 0,
+                redemption_keys: Map::new(),
+                redemption_nonces: Map::new(),
+                paused_assets: Map::new(),
+                next_redemption_id: 0,
+                sibling_vaults: Vec::new(),
             }
         }
         
-        /// Add backing assets (from consumer payments)
+        /// Add backing assets (from consumer payments). `max_usd` caps the credited
+        /// value at what the payer expected, in case the valuation spiked since.
         #[message]
         pub fn add_backing(
             &mut self,
@@ -55,11 +260,59 @@ pub mod grail_vault {
             amount: u128,
             usd_value: u64,
             tx_proof: [u8; 32],
+            max_usd: Option<u64>,
+        ) -> Result<(), String> {
+            self.add_backing_multi(chain, token_address, amount, usd_value, vec![tx_proof], max_usd)
+        }
+        
+        /// Add backing funded by several verified payment outputs whose amounts
+        /// sum to `amount`, crediting at most `max_usd` (DAO only)
+        #[message]
+        pub fn add_backing_multi(
+            &mut self,
+            chain: String,
+            token_address: String,
+            amount: u128,
+            usd_value: u64,
+            tx_proofs: Vec<[u8; 32]>,
+            max_usd: Option<u64>,
         ) -> Result<(), String> {
-            // Verify UTXO payment proof
-            self.verify_payment_proof(tx_proof)?;
+            // Only admin (DAO) can credit backing
+            self.ensure_admin()?;
+            
+            let usd_value = max_usd.map_or(usd_value, |max| usd_value.min(max));
+            
+            if amount == 0 || usd_value == 0 {
+                return Err("Zero backing amount".into());
+            }
+            
+            if usd_value < self.min_backing_usd {
+                return Err("Backing below minimum".into());
+            }
+            
+            if tx_proofs.is_empty() {
+                return Err("No payment proofs".into());
+            }
+            
+            // Verify every UTXO payment proof and that together they fund the credit
+            let mut outputs: Vec<([u8; 32], u128)> = Vec::new();
+            let mut paid: u128 = 0;
+            for txid in tx_proofs {
+                if outputs.iter().any(|(seen, _)| *seen == txid) {
+                    return Err("Duplicate payment proof".into());
+                }
+                let payment = self.verified_payment(txid, &chain)?;
+                paid = paid.checked_add(payment.amount as u128).ok_or("Backing overflow")?;
+                outputs.push((txid, payment.amount as u128));
+            }
+            if paid != amount {
+                return Err("Payment does not match backing".into());
+            }
             
             let key = format!("{}:{}", chain, token_address);
+            if self.paused_assets.get(&key).unwrap_or(false) {
+                return Err("Backing asset paused".into());
+            }
             
             let mut asset = self.backing_assets.get(&key).unwrap_or(BackingAsset {
                 chain: chain.clone(),
@@ -68,48 +321,1972 @@ pub mod grail_vault {
                 usd_value: 0,
             });
             
-            asset.amount += amount;
-            asset.usd_value += usd_value;
+            asset.amount = asset.amount.checked_add(amount).ok_or("Backing overflow")?;
+            asset.usd_value = asset.usd_value.checked_add(usd_value).ok_or("Backing overflow")?;
+            let total_backing_usd = self.total_backing_usd
+                .checked_add(usd_value as u128)
+                .ok_or("Backing overflow")?;
+            
+            self.roll_growth_window();
+            
+            self.backing_assets.insert(key.clone(), asset);
+            self.total_backing_usd = total_backing_usd;
+            
+            // Attribute the USD credit to each output pro rata; the last takes the dust
+            let mut usd_left = usd_value;
+            let last = outputs.len() - 1;
+            for (i, (txid, output_amount)) in outputs.into_iter().enumerate() {
+                let usd = if i == last {
+                    usd_left
+                } else {
+                    ((usd_value as u128)
+                        .checked_mul(output_amount)
+                        .ok_or("Backing overflow")?
+                        / amount) as u64
+                };
+                usd_left -= usd;
+                self.credited_payments.insert(txid, true);
+                self.payment_backing.insert(txid, (key.clone(), output_amount, usd));
+            }
+            
+            let eligible = self.mint_eligible_backing_usd();
+            if eligible < self.total_backing_usd {
+                wasm::emit_event("BackingThrottled", &BackingThrottledEvent {
+                    total_backing_usd: self.total_backing_usd,
+                    mint_eligible_usd: eligible,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            Ok(())
+        }
+        
+        /// Undo the backing credited by a payment the UTXO verifier has since
+        /// revoked. The txid stays marked as credited so it can't be re-added. (DAO only)
+        #[message]
+        pub fn reverse_backing(&mut self, txid: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            let (key, amount, usd_value) = self.payment_backing
+                .get(&txid)
+                .ok_or("No backing credited for payment")?;
+            
+            // The verifier stops serving a payment once it is revoked
+            let payment: Option<UTXOPayment> =
+                wasm::call_view(&self.utxo_verifier, "get_payment", &txid)?;
+            if payment.is_some() {
+                return Err("Payment not revoked".into());
+            }
+            
+            let mut asset = self.backing_assets.get(&key).ok_or("Unknown backing asset")?;
+            asset.amount = asset.amount.checked_sub(amount).ok_or("Backing already released")?;
+            asset.usd_value = asset.usd_value.checked_sub(usd_value).ok_or("Backing already released")?;
+            let total_backing_usd = self.total_backing_usd
+                .checked_sub(usd_value as u128)
+                .ok_or("Backing already released")?;
+            
+            if asset.amount == 0 && asset.usd_value == 0 {
+                self.backing_assets.remove(&key);
+            } else {
+                self.backing_assets.insert(key.clone(), asset);
+            }
+            self.total_backing_usd = total_backing_usd;
+            self.payment_backing.remove(&txid);
+            
+            wasm::emit_event("BackingReversed", &BackingReversedEvent {
+                txid,
+                asset_key: key,
+                amount,
+                usd_value,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Backing a verified payment credited: (asset key, amount, usd)
+        #[view]
+        pub fn payment_backing_of(&self, txid: [u8; 32]) -> Option<(String, u128, u64)> {
+            self.payment_backing.get(&txid)
+        }
+        
+        /// Move backing out of the vault for rebalancing or off-chain settlement (DAO only)
+        #[message]
+        pub fn withdraw_backing(
+            &mut self,
+            chain: String,
+            token_address: String,
+            amount: u128,
+            usd_value: u64,
+            recipient: String,
+        ) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            let key = format!("{}:{}", chain, token_address);
+            let mut asset = self.backing_assets.get(&key).ok_or("Unknown backing asset")?;
+            
+            asset.amount = asset.amount.checked_sub(amount).ok_or("Withdrawal exceeds holdings")?;
+            asset.usd_value = asset.usd_value.checked_sub(usd_value).ok_or("Withdrawal exceeds holdings")?;
+            
+            // Backing promised to queued redemptions can't be withdrawn
+            if usd_value as u128 > self.unreserved_backing_usd() {
+                return Err("Withdrawal exceeds unreserved backing".into());
+            }
+            let total_backing_usd = self.total_backing_usd - usd_value as u128;
+            
+            let effective_after = self.effective_backing_usd()
+                .saturating_sub(self.haircut_usd(&key, usd_value as u128));
+            let ratio_after = self.ratio_bps(
+                effective_after.saturating_sub(self.reserved_backing_usd),
+                self.zkbtce_supply,
+            );
+            if ratio_after < self.min_collateral_bps {
+                return Err("Would breach collateral floor".into());
+            }
+            
+            if asset.amount == 0 && asset.usd_value == 0 {
+                self.backing_assets.remove(&key);
+            } else {
+                self.backing_assets.insert(key, asset);
+            }
+            self.total_backing_usd = total_backing_usd;
             
-            self.backing_assets.insert(key, asset);
-            self.total_backing_usd += usd_value as u128;
+            wasm::emit_event("BackingWithdrawn", &BackingWithdrawnEvent {
+                chain,
+                token_address,
+                amount,
+                usd_value,
+                recipient,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
             
             Ok(())
         }
         
-        /// Request redemption (burn zkBTC-E for backing assets)
+        /// Request redemption (burn zkBTC-E for backing assets held on `dest_chain`)
         #[message]
         pub fn request_redemption(
             &mut self,
             amount: u64,
+            dest_chain: String,
+            recipient: String,
+        ) -> Result<(), String> {
+            self.enqueue_redemption(wasm::caller(), amount, dest_chain, recipient)
+        }
+        
+        /// Redirect a queued redemption whose recipient became unusable. The owner's
+        /// redemption key signs `reassignment_auth_bytes` for the entry at
+        /// `queue_index` at their next nonce, so the signature can't land on another
+        /// entry after the queue shifts.
+        #[message]
+        pub fn reassign_redemption(
+            &mut self,
+            queue_index: u32,
+            new_recipient: String,
+            auth_sig: [u8; 64],
+        ) -> Result<(), String> {
+            if new_recipient.is_empty() {
+                return Err("Recipient must not be empty".into());
+            }
+            
+            let mut request = self.redemption_queue
+                .get(queue_index as usize)
+                .cloned()
+                .ok_or("No queued redemption at index")?;
+            
+            let key = self.redemption_keys.get(&request.owner).ok_or("No redemption key for owner")?;
+            let nonce = self.redemption_nonces.get(&request.owner).unwrap_or(0);
+            let message = reassignment_auth_bytes(
+                &wasm::self_address(),
+                &request.owner,
+                request.id,
+                &request.recipient,
+                &new_recipient,
+                nonce,
+            );
+            
+            let public_key = PublicKey::from_bytes(&key).map_err(|_| "Invalid redemption key")?;
+            let signature = Signature::from_bytes(&auth_sig).map_err(|_| "Malformed authorization")?;
+            public_key
+                .verify(&message, &signature)
+                .map_err(|_| "Invalid reassignment authorization")?;
+            
+            self.redemption_nonces.insert(request.owner.clone(), nonce + 1);
+            let old_recipient = std::mem::replace(&mut request.recipient, new_recipient.clone());
+            let id = request.id;
+            self.redemption_queue[queue_index as usize] = request;
+            
+            wasm::emit_event("RedemptionReassigned", &RedemptionReassignedEvent {
+                id,
+                old_recipient,
+                new_recipient,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Withdraw a queued redemption before it is processed, returning the tokens
+        /// and releasing the backing reserved for it. The owner's redemption key
+        /// signs `cancellation_auth_bytes` for the entry at `queue_index`; processed
+        /// entries have left the queue and can't be cancelled.
+        #[message]
+        pub fn cancel_redemption(&mut self, queue_index: u32, auth_sig: [u8; 64]) -> Result<(), String> {
+            let request = self.redemption_queue
+                .get(queue_index as usize)
+                .cloned()
+                .ok_or("No queued redemption at index")?;
+            
+            let key = self.redemption_keys.get(&request.owner).ok_or("No redemption key for owner")?;
+            let nonce = self.redemption_nonces.get(&request.owner).unwrap_or(0);
+            
+            let public_key = PublicKey::from_bytes(&key).map_err(|_| "Invalid redemption key")?;
+            let signature = Signature::from_bytes(&auth_sig).map_err(|_| "Malformed authorization")?;
+            public_key
+                .verify(
+                    &cancellation_auth_bytes(&wasm::self_address(), &request.owner, request.id, nonce),
+                    &signature,
+                )
+                .map_err(|_| "Invalid cancellation authorization")?;
+            
+            let reserved = self.reserved_backing_usd
+                .checked_sub(request.usd_value as u128)
+                .ok_or("Arithmetic overflow")?;
+            let supply = self.zkbtce_supply
+                .checked_add(request.amount)
+                .ok_or("Arithmetic overflow")?;
+            
+            self.refund_holder_tokens(&request.owner, request.amount)?;
+            
+            self.redemption_nonces.insert(request.owner.clone(), nonce + 1);
+            self.redemption_queue.remove(queue_index as usize);
+            self.reserved_backing_usd = reserved;
+            self.zkbtce_supply = supply;
+            
+            let depth = self.redemption_queue.len() as u32;
+            wasm::emit_event("RedemptionDequeued", &RedemptionDequeuedEvent {
+                id: request.id,
+                recipient: request.recipient,
+                amount: request.amount,
+                queue_depth: depth,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            wasm::emit_event("RedemptionCancelled", &RedemptionCancelledEvent {
+                id: request.id,
+                owner: request.owner,
+                amount: request.amount,
+                usd_value: request.usd_value,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            wasm::emit_event("QueueDepthChanged", &QueueDepthChangedEvent {
+                queue_depth: depth,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Register the key that authorizes redemptions of the caller's tokens by others
+        #[message]
+        pub fn set_redemption_key(&mut self, key: [u8; 32]) -> Result<(), String> {
+            PublicKey::from_bytes(&key).map_err(|_| "Invalid redemption key")?;
+            
+            self.redemption_keys.insert(wasm::caller(), key);
+            Ok(())
+        }
+        
+        /// Queue redemptions for many holders in one call, e.g. from a custodian.
+        /// Each entry is (owner, amount, dest_chain, recipient, signature) where the
+        /// owner's redemption key signed `redemption_auth_bytes` at their next nonce.
+        /// Queues every entry or none.
+        #[message]
+        pub fn request_redemptions_batch(
+            &mut self,
+            entries: Vec<(String, u64, String, String, [u8; 64])>,
+        ) -> Result<u32, String> {
+            // Check every authorization before queueing anything
+            let vault = wasm::self_address();
+            let mut nonces: BTreeMap<String, u64> = BTreeMap::new();
+            let mut total_usd: u128 = 0;
+            for (owner, amount, dest_chain, recipient, auth_sig) in &entries {
+                let key = self.redemption_keys.get(owner).ok_or("No redemption key for owner")?;
+                let nonce = nonces
+                    .entry(owner.clone())
+                    .or_insert_with(|| self.redemption_nonces.get(owner).unwrap_or(0));
+                
+                let public_key = PublicKey::from_bytes(&key).map_err(|_| "Invalid redemption key")?;
+                let signature = Signature::from_bytes(auth_sig).map_err(|_| "Malformed authorization")?;
+                public_key
+                    .verify(
+                        &redemption_auth_bytes(&vault, owner, *amount, dest_chain, recipient, *nonce),
+                        &signature,
+                    )
+                    .map_err(|_| "Invalid redemption authorization")?;
+                *nonce += 1;
+                
+                let usd_value = self.redemption_usd(*amount)?;
+                total_usd = total_usd.checked_add(usd_value as u128).ok_or("Arithmetic overflow")?;
+            }
+            
+            if total_usd > self.unreserved_backing_usd() {
+                return Err("Insufficient backing assets".into());
+            }
+            
+            for (owner, nonce) in nonces {
+                self.redemption_nonces.insert(owner, nonce);
+            }
+            
+            let count = entries.len() as u32;
+            for (owner, amount, dest_chain, recipient, _) in entries {
+                self.enqueue_redemption(owner, amount, dest_chain, recipient)?;
+            }
+            
+            Ok(count)
+        }
+        
+        /// USD value of `amount` tokens at the live price; an unset price would
+        /// burn tokens against no backing
+        fn redemption_usd(&self, amount: u64) -> Result<u64, String> {
+            if self.price_per_token_usd == 0 {
+                return Err("Price not set".into());
+            }
+            
+            amount
+                .checked_mul(self.price_per_token_usd)
+                .ok_or_else(|| "Arithmetic overflow".into())
+        }
+        
+        /// Burn `owner`'s tokens and queue the redemption, reserving its backing
+        fn enqueue_redemption(
+            &mut self,
+            owner: String,
+            amount: u64,
+            dest_chain: String,
             recipient: String,
         ) -> Result<(), String> {
-            // Calculate USD value
-            let usd_value = amount * 70; // $70 per zkBTC-E
+            if amount == 0 {
+                return Err("Zero redemption amount".into());
+            }
+            
+            if dest_chain.is_empty() {
+                return Err("Destination chain must not be empty".into());
+            }
+            
+            // Calculate USD value at the live price
+            let usd_value = self.redemption_usd(amount)?;
             
-            // Check sufficient backing
-            if (usd_value as u128) > self.total_backing_usd {
+            // Check sufficient backing not already promised to the queue
+            if (usd_value as u128) > self.unreserved_backing_usd() {
                 return Err("Insufficient backing assets".into());
             }
             
-            // Add to redemption queue
-            self.redemption_queue.push((recipient, amount));
+            let supply = self.zkbtce_supply.checked_sub(amount).ok_or("Arithmetic overflow")?;
+            let reserved = self.reserved_backing_usd
+                .checked_add(usd_value as u128)
+                .ok_or("Arithmetic overflow")?;
+            
+            let ratio_after = self.ratio_bps(self.effective_backing_usd().saturating_sub(reserved), supply);
+            if ratio_after < self.min_collateral_bps {
+                return Err("Would breach collateral floor".into());
+            }
+            
+            // Burn the holder's tokens on the minter
+            self.burn_holder_tokens(&owner, amount)?;
+            
+            // Add to redemption queue, reserving its backing
+            let id = self.next_redemption_id;
+            self.redemption_queue.push(RedemptionRequest {
+                id,
+                owner,
+                dest_chain: dest_chain.clone(),
+                recipient: recipient.clone(),
+                amount,
+                usd_value,
+                enqueued_at: wasm::block_timestamp(),
+            });
+            self.next_redemption_id += 1;
+            self.reserved_backing_usd = reserved;
             
             // Update supply
-            self.zkbtce_supply -= amount;
+            self.zkbtce_supply = supply;
+            
+            wasm::emit_event("RedemptionRequested", &RedemptionRequestedEvent {
+                recipient: recipient.clone(),
+                amount,
+                usd_value,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
             
-            wasm::emit_event("RedemptionRequested", &(recipient, amount, usd_value));
+            // Queue mirror events for indexers
+            let depth = self.redemption_queue.len() as u32;
+            wasm::emit_event("RedemptionEnqueued", &RedemptionEnqueuedEvent {
+                id,
+                dest_chain,
+                recipient,
+                amount,
+                usd_value,
+                queue_depth: depth,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            wasm::emit_event("QueueDepthChanged", &QueueDepthChangedEvent {
+                queue_depth: depth,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
             
             Ok(())
         }
         
-        fn verify_payment_proof(&self, proof: [u8; 32]) -> Result<(), String> {
-            // In reality: Verify Bitcoin/Litecoin/Cardano transaction
-            // For now, accept any non-zero proof
-            if proof == [0u8; 32] {
-                return Err("Invalid payment proof".into());
+        /// Redeem to a chain: released directly when the vault holds enough backing
+        /// there, otherwise queued for the cross-chain bridge. Returns true if direct.
+        #[message]
+        pub fn redeem_to_chain(
+            &mut self,
+            amount: u64,
+            dest_chain: String,
+            recipient: String,
+        ) -> Result<bool, String> {
+            if amount == 0 {
+                return Err("Zero redemption amount".into());
+            }
+            
+            let usd_value = self.redemption_usd(amount)?;
+            
+            // Only unpaused assets can be drawn; otherwise fall back to the queue
+            let chain_usd = self.redeemable_backing_usd(&dest_chain);
+            
+            if chain_usd >= usd_value as u128 && self.unreserved_backing_usd() >= usd_value as u128 {
+                self.direct_release(amount, usd_value, dest_chain, recipient)?;
+                Ok(true)
+            } else {
+                self.request_redemption(amount, dest_chain, recipient)?;
+                Ok(false)
+            }
+        }
+        
+        /// Settle queued redemptions, batching payouts per destination chain (DAO only)
+        #[message]
+        pub fn process_redemptions(&mut self, max_items: u32) -> Result<Vec<(String, u64)>, String> {
+            self.ensure_admin()?;
+            
+            let limit = (max_items as usize).min(self.redemption_queue.len());
+            
+            // Partial fill: settle in queue order while unpaused backing on the next
+            // request's chain still covers it, leaving the rest queued in their
+            // original order
+            let mut available: BTreeMap<String, u128> = BTreeMap::new();
+            let mut count = 0;
+            for request in &self.redemption_queue[..limit] {
+                let left = available
+                    .entry(request.dest_chain.clone())
+                    .or_insert_with(|| self.redeemable_backing_usd(&request.dest_chain));
+                if request.usd_value as u128 > *left {
+                    break;
+                }
+                *left -= request.usd_value as u128;
+                count += 1;
+            }
+            
+            if count == 0 && limit > 0 {
+                return Err("Insufficient backing assets".into());
+            }
+            
+            let batch: Vec<RedemptionRequest> = self.redemption_queue.drain(..count).collect();
+            
+            // chain -> [(recipient, token_address, amount, usd)]
+            let mut payouts: BTreeMap<String, Vec<(String, String, u128, u64)>> = BTreeMap::new();
+            let mut settled = Vec::new();
+            let mut depth = self.redemption_queue.len() as u32 + count as u32;
+            for RedemptionRequest { id, dest_chain, recipient, amount, usd_value, .. } in batch {
+                self.reserved_backing_usd = self.reserved_backing_usd
+                    .checked_sub(usd_value as u128)
+                    .ok_or("Arithmetic overflow")?;
+                
+                for (asset, released, usd) in self.release_backing(usd_value, Some(&dest_chain))? {
+                    payouts.entry(dest_chain.clone()).or_default().push((
+                        recipient.clone(),
+                        asset.token_address,
+                        released,
+                        usd,
+                    ));
+                }
+                
+                depth -= 1;
+                wasm::emit_event("RedemptionDequeued", &RedemptionDequeuedEvent {
+                    id,
+                    recipient: recipient.clone(),
+                    amount,
+                    queue_depth: depth,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+                wasm::emit_event("RedemptionSettled", &RedemptionSettledEvent {
+                    recipient: recipient.clone(),
+                    amount,
+                    usd_value,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+                settled.push((recipient, amount));
+            }
+            
+            if count > 0 {
+                wasm::emit_event("QueueDepthChanged", &QueueDepthChangedEvent {
+                    queue_depth: depth,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            // One aggregated payout per chain keeps cross-chain transactions down
+            for (chain, items) in payouts {
+                let total_usd: u64 = items.iter().map(|(_, _, _, usd)| *usd).sum();
+                wasm::emit_event("ChainPayout", &ChainPayoutEvent {
+                    chain,
+                    items,
+                    total_usd,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            Ok(settled)
+        }
+        
+        /// Drop queued redemptions older than `queue_entry_ttl` (callable by any keeper)
+        #[message]
+        pub fn expire_redemptions(&mut self, now: u64) -> Result<u32, String> {
+            if now > wasm::block_timestamp() {
+                return Err("Timestamp in the future".into());
+            }
+            
+            if self.queue_entry_ttl == 0 {
+                return Ok(0);
+            }
+            
+            let ttl = self.queue_entry_ttl;
+            let (expired, kept): (Vec<_>, Vec<_>) = self.redemption_queue
+                .drain(..)
+                .partition(|request| now.saturating_sub(request.enqueued_at) > ttl);
+            self.redemption_queue = kept;
+            
+            let refunded = self.expiry_policy == ExpiryPolicy::Refund;
+            for request in &expired {
+                self.reserved_backing_usd = self.reserved_backing_usd
+                    .checked_sub(request.usd_value as u128)
+                    .ok_or("Arithmetic overflow")?;
+                
+                if refunded {
+                    self.zkbtce_supply = self.zkbtce_supply
+                        .checked_add(request.amount)
+                        .ok_or("Arithmetic overflow")?;
+                    self.refund_holder_tokens(&request.owner, request.amount)?;
+                }
+                
+                wasm::emit_event("RedemptionExpired", &RedemptionExpiredEvent {
+                    recipient: request.recipient.clone(),
+                    amount: request.amount,
+                    enqueued_at: request.enqueued_at,
+                    refunded,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            if !expired.is_empty() {
+                wasm::emit_event("QueueDepthChanged", &QueueDepthChangedEvent {
+                    queue_depth: self.redemption_queue.len() as u32,
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            Ok(expired.len() as u32)
+        }
+        
+        /// Start handing admin to `new_admin`; takes effect once they accept (DAO only)
+        #[message]
+        pub fn propose_admin(&mut self, new_admin: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if new_admin.is_empty() {
+                return Err("Admin must not be empty".into());
             }
+            
+            self.pending_admin = Some(new_admin.clone());
+            wasm::emit_event("AdminTransferStarted", &AdminTransferStartedEvent {
+                admin: self.admin.clone(),
+                pending_admin: new_admin,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
             Ok(())
         }
+        
+        /// Complete an admin handoff (proposed admin only)
+        #[message]
+        pub fn accept_admin(&mut self) -> Result<(), String> {
+            let caller = wasm::caller();
+            if self.pending_admin.as_ref() != Some(&caller) {
+                return Err("Caller is not pending admin".into());
+            }
+            
+            let previous = std::mem::replace(&mut self.admin, caller.clone());
+            self.pending_admin = None;
+            
+            wasm::emit_event("AdminTransferCompleted", &AdminTransferCompletedEvent {
+                previous_admin: previous,
+                admin: caller,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Configure redemption queue expiry (DAO only)
+        #[message]
+        pub fn set_queue_expiry(&mut self, ttl: u64, policy: ExpiryPolicy) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.queue_entry_ttl = ttl;
+            self.expiry_policy = policy;
+            Ok(())
+        }
+        
+        /// Point deposits at the UTXOVerifier contract (DAO only)
+        #[message]
+        pub fn set_utxo_verifier(&mut self, verifier: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if verifier.is_empty() {
+                return Err("Verifier must not be empty".into());
+            }
+            
+            self.utxo_verifier = verifier.clone();
+            wasm::emit_event("UtxoVerifierUpdated", &ContractLinkUpdatedEvent {
+                address: verifier,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Point redemptions at the PoE minter holding zkBTC-E balances (DAO only)
+        #[message]
+        pub fn set_minter(&mut self, minter: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if minter.is_empty() {
+                return Err("Minter must not be empty".into());
+            }
+            
+            self.minter = minter.clone();
+            wasm::emit_event("MinterUpdated", &ContractLinkUpdatedEvent {
+                address: minter,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Count newly minted zkBTC-E in circulating supply (minter only)
+        #[message]
+        pub fn record_mint(&mut self, amount: u64) -> Result<(), String> {
+            self.ensure_minter()?;
+            
+            self.zkbtce_supply = self.zkbtce_supply
+                .checked_add(amount)
+                .ok_or("Arithmetic overflow")?;
+            Ok(())
+        }
+        
+        /// Count another regional vault in `aggregate_reserves` (DAO only)
+        #[message]
+        pub fn register_sibling_vault(&mut self, vault: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if vault.is_empty() {
+                return Err("Vault must not be empty".into());
+            }
+            if vault == wasm::self_address() {
+                return Err("Vault cannot be its own sibling".into());
+            }
+            if self.sibling_vaults.contains(&vault) {
+                return Err("Sibling vault already registered".into());
+            }
+            if self.sibling_vaults.len() >= MAX_SIBLING_VAULTS {
+                return Err("Too many sibling vaults".into());
+            }
+            
+            self.sibling_vaults.push(vault.clone());
+            wasm::emit_event("SiblingVaultRegistered", &ContractLinkUpdatedEvent {
+                address: vault,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Stop counting a sibling vault in `aggregate_reserves` (DAO only)
+        #[message]
+        pub fn remove_sibling_vault(&mut self, vault: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            let index = self.sibling_vaults
+                .iter()
+                .position(|sibling| *sibling == vault)
+                .ok_or("Sibling vault not registered")?;
+            
+            self.sibling_vaults.remove(index);
+            wasm::emit_event("SiblingVaultRemoved", &ContractLinkUpdatedEvent {
+                address: vault,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the USD value redeemed per zkBTC-E (DAO only)
+        #[message]
+        pub fn set_price_per_token(&mut self, new_price: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if new_price == 0 {
+                return Err("Price must be non-zero".into());
+            }
+            
+            self.price_per_token_usd = new_price;
+            wasm::emit_event("PriceUpdated", &PriceUpdatedEvent {
+                price_per_token_usd: new_price,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Stop accepting deposits of an asset and drawing it for redemptions,
+        /// e.g. while its chain is halted (DAO only)
+        #[message]
+        pub fn pause_asset(&mut self, key: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            self.paused_assets.insert(key.clone(), true);
+            wasm::emit_event("AssetPaused", &AssetPauseEvent {
+                asset_key: key,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Resume deposits and redemptions of a paused asset (DAO only)
+        #[message]
+        pub fn unpause_asset(&mut self, key: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if self.paused_assets.remove(&key).is_none() {
+                return Err("Asset not paused".into());
+            }
+            wasm::emit_event("AssetUnpaused", &AssetPauseEvent {
+                asset_key: key,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Set the solvency haircut for a backing asset (DAO only)
+        #[message]
+        pub fn set_haircut(&mut self, key: String, bps: u16) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if bps > BPS_DENOMINATOR {
+                return Err("Haircut exceeds 100%".into());
+            }
+            
+            self.haircut_bps.insert(key.clone(), bps);
+            wasm::emit_event("HaircutUpdated", &AssetBpsUpdatedEvent {
+                asset_key: key,
+                bps,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Set an asset's target share of total backing; 0 clears it (DAO only)
+        #[message]
+        pub fn set_target_weight(&mut self, key: String, bps: u16) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            let others: u32 = self.target_weights
+                .iter()
+                .filter(|(k, _)| *k != key)
+                .map(|(_, w)| w as u32)
+                .sum();
+            if others + bps as u32 > BPS_DENOMINATOR as u32 {
+                return Err("Target weights exceed 100%".into());
+            }
+            
+            if bps == 0 {
+                self.target_weights.remove(&key);
+            } else {
+                self.target_weights.insert(key.clone(), bps);
+            }
+            wasm::emit_event("TargetWeightUpdated", &AssetBpsUpdatedEvent {
+                asset_key: key,
+                bps,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Log an executed off-chain swap and move backing from one asset to another (DAO only)
+        #[message]
+        pub fn record_rebalance(
+            &mut self,
+            sold_key: String,
+            sold_amount: u128,
+            sold_usd: u64,
+            bought_chain: String,
+            bought_token_address: String,
+            bought_amount: u128,
+            bought_usd: u64,
+        ) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.roll_growth_window();
+            
+            let mut sold = self.backing_assets.get(&sold_key).ok_or("Unknown backing asset")?;
+            sold.amount = sold.amount.checked_sub(sold_amount).ok_or("Rebalance exceeds holdings")?;
+            sold.usd_value = sold.usd_value.checked_sub(sold_usd).ok_or("Rebalance exceeds holdings")?;
+            // A position marked to zero may still hold tokens; only drop it once empty
+            if sold.amount == 0 && sold.usd_value == 0 {
+                self.backing_assets.remove(&sold_key);
+            } else {
+                self.backing_assets.insert(sold_key.clone(), sold);
+            }
+            
+            let bought_key = format!("{}:{}", bought_chain, bought_token_address);
+            let mut bought = self.backing_assets.get(&bought_key).unwrap_or(BackingAsset {
+                chain: bought_chain,
+                token_address: bought_token_address,
+                amount: 0,
+                usd_value: 0,
+            });
+            bought.amount = bought.amount.checked_add(bought_amount).ok_or("Arithmetic overflow")?;
+            bought.usd_value = bought.usd_value.checked_add(bought_usd).ok_or("Arithmetic overflow")?;
+            self.backing_assets.insert(bought_key.clone(), bought);
+            
+            // Swap slippage moves total backing, which must still cover the queue
+            self.total_backing_usd = self.total_backing_usd
+                .checked_sub(sold_usd as u128)
+                .and_then(|total| total.checked_add(bought_usd as u128))
+                .ok_or("Arithmetic overflow")?;
+            if self.total_backing_usd < self.reserved_backing_usd {
+                return Err("Rebalance would undercover queued redemptions".into());
+            }
+            
+            wasm::emit_event("Rebalanced", &RebalancedEvent {
+                sold_key,
+                sold_amount,
+                sold_usd,
+                bought_key,
+                bought_amount,
+                bought_usd,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Set the Ed25519 key whose reserve attestations are accepted (DAO only)
+        #[message]
+        pub fn set_reserve_attester(&mut self, attester: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            PublicKey::from_bytes(&attester).map_err(|_| "Invalid attester key")?;
+            
+            self.reserve_attester = Some(attester);
+            wasm::emit_event("ReserveAttesterUpdated", &ReserveAttesterUpdatedEvent {
+                attester,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Publish the attester's signature over a snapshot of the current reserves.
+        /// The attester signs off-chain (its key never touches the chain); the
+        /// snapshot must match live state so the attestation can't vouch for stale numbers.
+        #[message]
+        pub fn attest_reserves(
+            &mut self,
+            snapshot: ReserveSnapshot,
+            signature: [u8; 64],
+        ) -> Result<(), String> {
+            let attester = self.reserve_attester.ok_or("No reserve attester")?;
+            
+            let live = self.reserve_snapshot();
+            if snapshot.total_backing_usd != live.total_backing_usd
+                || snapshot.effective_backing_usd != live.effective_backing_usd
+                || snapshot.reserved_backing_usd != live.reserved_backing_usd
+                || snapshot.zkbtce_supply != live.zkbtce_supply
+                || snapshot.taken_at > live.taken_at
+            {
+                return Err("Snapshot does not match reserves".into());
+            }
+            
+            let public_key = PublicKey::from_bytes(&attester).map_err(|_| "Invalid attester key")?;
+            let signature = Signature::from_bytes(&signature).map_err(|_| "Malformed attestation signature")?;
+            public_key
+                .verify(&snapshot.encode(), &signature)
+                .map_err(|_| "Invalid attestation signature")?;
+            
+            wasm::emit_event("ReservesAttested", &ReservesAttestedEvent {
+                snapshot,
+                signature: signature.to_bytes(),
+                attester,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Cap how fast mint-eligible backing may grow: `bps` per `window` seconds,
+        /// 0 bps for no cap (DAO only)
+        #[message]
+        pub fn set_backing_growth_cap(&mut self, bps: u16, window: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if window == 0 {
+                return Err("Window must be non-zero".into());
+            }
+            
+            // Restart the window from what is currently eligible; seeding it here
+            // keeps the first window capped too
+            self.growth_window_base = self.mint_eligible_backing_usd();
+            self.growth_window_start = wasm::block_timestamp();
+            self.backing_growth_cap_bps = bps;
+            self.backing_growth_window = window;
+            
+            wasm::emit_event("BackingGrowthCapUpdated", &BackingGrowthCapUpdatedEvent {
+                bps,
+                window,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the collateral ratio floor in bps (10000 = fully backed, 0 = off) (DAO only)
+        #[message]
+        pub fn set_min_collateral_bps(&mut self, bps: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.min_collateral_bps = bps;
+            wasm::emit_event("MinCollateralUpdated", &MinCollateralUpdatedEvent {
+                bps,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the minimum USD value accepted per deposit (DAO only)
+        #[message]
+        pub fn set_min_backing_usd(&mut self, min_usd: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.min_backing_usd = min_usd;
+            wasm::emit_event("MinBackingUpdated", &MinBackingUpdatedEvent {
+                min_usd,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Current reserves, in the form the attester signs (SCALE-encoded)
+        #[view]
+        pub fn reserve_snapshot(&self) -> ReserveSnapshot {
+            ReserveSnapshot {
+                total_backing_usd: self.total_backing_usd,
+                effective_backing_usd: self.effective_backing_usd(),
+                reserved_backing_usd: self.reserved_backing_usd,
+                zkbtce_supply: self.zkbtce_supply,
+                taken_at: wasm::block_timestamp(),
+            }
+        }
+        
+        /// Combined reserves of this vault and its siblings, read through each
+        /// sibling's `reserve_snapshot`. Unreachable siblings are listed rather
+        /// than failing the query; the ratio uses this vault's price.
+        #[view]
+        pub fn aggregate_reserves(&self) -> AggregateReserves {
+            let mut aggregate = AggregateReserves {
+                total_backing_usd: self.total_backing_usd,
+                effective_backing_usd: self.effective_backing_usd(),
+                reserved_backing_usd: self.reserved_backing_usd,
+                zkbtce_supply: self.zkbtce_supply,
+                collateral_ratio_bps: 0,
+                vaults_counted: 1,
+                unreachable: Vec::new(),
+            };
+            
+            for sibling in &self.sibling_vaults {
+                let snapshot: Result<ReserveSnapshot, String> =
+                    wasm::call_view(sibling, "reserve_snapshot", &());
+                match snapshot {
+                    Ok(snapshot) => {
+                        aggregate.total_backing_usd =
+                            aggregate.total_backing_usd.saturating_add(snapshot.total_backing_usd);
+                        aggregate.effective_backing_usd =
+                            aggregate.effective_backing_usd.saturating_add(snapshot.effective_backing_usd);
+                        aggregate.reserved_backing_usd =
+                            aggregate.reserved_backing_usd.saturating_add(snapshot.reserved_backing_usd);
+                        aggregate.zkbtce_supply = aggregate.zkbtce_supply.saturating_add(snapshot.zkbtce_supply);
+                        aggregate.vaults_counted += 1;
+                    }
+                    Err(_) => aggregate.unreachable.push(sibling.clone()),
+                }
+            }
+            
+            // Haircut backing, matching each vault's own `collateral_ratio_bps`
+            aggregate.collateral_ratio_bps = self.ratio_bps(
+                aggregate.effective_backing_usd.saturating_sub(aggregate.reserved_backing_usd),
+                aggregate.zkbtce_supply,
+            );
+            aggregate
+        }
+        
+        /// Backing USD counted for minting eligibility: increases beyond the growth
+        /// cap are throttled until later windows, decreases apply immediately.
+        /// Backing credited while the base is still zero waits for the next window.
+        #[view]
+        pub fn mint_eligible_backing_usd(&self) -> u128 {
+            if self.backing_growth_cap_bps == 0 {
+                return self.total_backing_usd;
+            }
+            
+            let step = |base: u128| base + base * self.backing_growth_cap_bps as u128 / BPS_DENOMINATOR as u128;
+            
+            let mut ceiling = step(self.growth_window_base);
+            if wasm::block_timestamp() >= self.growth_window_start.saturating_add(self.backing_growth_window) {
+                // The window has lapsed without a roll; allow the next window's growth too
+                ceiling = if self.growth_window_base == 0 {
+                    self.total_backing_usd
+                } else {
+                    step(ceiling.min(self.total_backing_usd))
+                };
+            }
+            
+            ceiling.min(self.total_backing_usd)
+        }
+        
+        /// zkBTC-E the minter may still issue against mint-eligible backing not
+        /// reserved by queued redemptions, at the current price
+        #[view]
+        pub fn mint_capacity(&self) -> u64 {
+            if self.price_per_token_usd == 0 {
+                return 0;
+            }
+            
+            let eligible = self.mint_eligible_backing_usd().saturating_sub(self.reserved_backing_usd);
+            let backed = eligible / self.price_per_token_usd as u128;
+            backed.saturating_sub(self.zkbtce_supply as u128).min(u64::MAX as u128) as u64
+        }
+        
+        /// Haircut backing not reserved by queued redemptions over circulating supply
+        /// at the current price, in bps. Zero supply is reported as `u64::MAX`
+        /// (fully collateralized).
+        #[view]
+        pub fn collateral_ratio_bps(&self) -> u64 {
+            self.ratio_bps(
+                self.effective_backing_usd().saturating_sub(self.reserved_backing_usd),
+                self.zkbtce_supply,
+            )
+        }
+        
+        /// Whether circulating supply is at least fully backed
+        #[view]
+        pub fn is_solvent(&self) -> bool {
+            self.collateral_ratio_bps() >= BPS_DENOMINATOR as u64
+        }
+        
+        /// Backing USD not reserved by queued redemptions
+        #[view]
+        pub fn unreserved_backing_usd(&self) -> u128 {
+            self.total_backing_usd.saturating_sub(self.reserved_backing_usd)
+        }
+        
+        /// Per asset, USD to buy (positive) or sell (negative) to reach its target weight
+        #[view]
+        pub fn rebalance_suggestions(&self) -> Vec<(String, i128)> {
+            let total = self.total_backing_usd as i128;
+            let mut suggestions: BTreeMap<String, i128> = BTreeMap::new();
+            
+            for (key, asset) in self.backing_assets.iter() {
+                suggestions.insert(key, -(asset.usd_value as i128));
+            }
+            for (key, bps) in self.target_weights.iter() {
+                let target = total * bps as i128 / BPS_DENOMINATOR as i128;
+                *suggestions.entry(key).or_insert(0) += target;
+            }
+            
+            suggestions
+                .into_iter()
+                .filter(|(_, delta)| *delta != 0)
+                .collect()
+        }
+        
+        /// Backing USD value after per-asset haircuts (used for solvency)
+        #[view]
+        pub fn effective_backing_usd(&self) -> u128 {
+            self.backing_assets
+                .iter()
+                .map(|(key, asset)| self.haircut_usd(&key, asset.usd_value as u128))
+                .sum()
+        }
+
+        
+        // Internal helper functions
+        fn ensure_admin(&self) -> Result<(), String> {
+            let caller = wasm::caller();
+            if self.admin.is_empty() || caller != self.admin {
+                return Err("Caller is not admin".into());
+            }
+            Ok(())
+        }
+        
+        fn ensure_minter(&self) -> Result<(), String> {
+            if self.minter.is_empty() || wasm::caller() != self.minter {
+                return Err("Caller is not minter".into());
+            }
+            Ok(())
+        }
+        
+        /// `usd` of an asset after its haircut
+        fn haircut_usd(&self, key: &str, usd: u128) -> u128 {
+            let haircut = self.haircut_bps.get(key).unwrap_or(0);
+            let kept_bps = BPS_DENOMINATOR.saturating_sub(haircut) as u128;
+            usd * kept_bps / BPS_DENOMINATOR as u128
+        }
+        
+        fn ratio_bps(&self, backing_usd: u128, supply: u64) -> u64 {
+            let supply_usd = supply as u128 * self.price_per_token_usd as u128;
+            if supply_usd == 0 {
+                return u64::MAX;
+            }
+            
+            let ratio = backing_usd.saturating_mul(BPS_DENOMINATOR as u128) / supply_usd;
+            ratio.min(u64::MAX as u128) as u64
+        }
+        
+        /// Start a new growth window from the current eligible backing once the old one lapses
+        fn roll_growth_window(&mut self) {
+            let now = wasm::block_timestamp();
+            if now < self.growth_window_start.saturating_add(self.backing_growth_window) {
+                return;
+            }
+            
+            // Carry over what the lapsed window allowed
+            let grown = self.growth_window_base
+                + self.growth_window_base * self.backing_growth_cap_bps as u128 / BPS_DENOMINATOR as u128;
+            self.growth_window_base = if self.growth_window_base == 0 {
+                self.total_backing_usd
+            } else {
+                grown.min(self.total_backing_usd)
+            };
+            self.growth_window_start = now;
+        }
+        
+        /// Debit a holder's zkBTC-E balance on the minter
+        fn burn_holder_tokens(&self, owner: &str, amount: u64) -> Result<(), String> {
+            if self.minter.is_empty() {
+                return Err("No minter configured".into());
+            }
+            wasm::call::<_, ()>(&self.minter, "vault_burn", &(owner.to_string(), amount))
+        }
+        
+        /// Return an expired or cancelled redemption's tokens to its holder on the minter
+        fn refund_holder_tokens(&self, owner: &str, amount: u64) -> Result<(), String> {
+            if self.minter.is_empty() {
+                return Err("No minter configured".into());
+            }
+            wasm::call::<_, ()>(&self.minter, "vault_refund", &(owner.to_string(), amount))
+        }
+        
+        /// Release same-chain backing straight to the recipient, bypassing the bridge
+        fn direct_release(
+            &mut self,
+            amount: u64,
+            usd_value: u64,
+            chain: String,
+            recipient: String,
+        ) -> Result<(), String> {
+            let supply = self.zkbtce_supply.checked_sub(amount).ok_or("Arithmetic overflow")?;
+            self.burn_holder_tokens(&wasm::caller(), amount)?;
+            
+            let released: Vec<(String, u128, u64)> = self.release_backing(usd_value, Some(&chain))?
+                .into_iter()
+                .map(|(asset, released, usd)| (asset.token_address, released, usd))
+                .collect();
+            
+            self.zkbtce_supply = supply;
+            
+            // Checked after the draw since the haircut depends on which assets were
+            // drawn; the error reverts the release with it
+            let ratio_after = self.ratio_bps(
+                self.effective_backing_usd().saturating_sub(self.reserved_backing_usd),
+                supply,
+            );
+            if ratio_after < self.min_collateral_bps {
+                return Err("Would breach collateral floor".into());
+            }
+            
+            wasm::emit_event("DirectRelease", &DirectReleaseEvent {
+                recipient,
+                chain,
+                amount,
+                released,
+                usd_value,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Backing held on `chain` in assets that aren't paused
+        fn redeemable_backing_usd(&self, chain: &str) -> u128 {
+            self.backing_assets
+                .iter()
+                .filter(|(key, asset)| asset.chain == chain && !self.paused_assets.get(key).unwrap_or(false))
+                .map(|(_, asset)| asset.usd_value as u128)
+                .sum()
+        }
+        
+        /// Draw `usd_value` of backing proportionally across unpaused assets (optionally
+        /// only those on `chain`), returning (asset, token amount, usd) per asset drawn
+        fn release_backing(
+            &mut self,
+            usd_value: u64,
+            chain: Option<&str>,
+        ) -> Result<Vec<(BackingAsset, u128, u64)>, String> {
+            let eligible: Vec<(String, BackingAsset)> = self.backing_assets
+                .iter()
+                .filter(|(key, asset)| {
+                    chain.map_or(true, |c| asset.chain == c)
+                        && !self.paused_assets.get(key).unwrap_or(false)
+                })
+                .collect();
+            
+            let total: u128 = eligible.iter().map(|(_, asset)| asset.usd_value as u128).sum();
+            if usd_value as u128 > total {
+                return Err("Insufficient backing assets".into());
+            }
+            
+            // Both factors fit in u64, so the product can't overflow u128
+            let mut draws: Vec<(String, BackingAsset, u64)> = eligible
+                .into_iter()
+                .map(|(key, asset)| {
+                    let share = ((asset.usd_value as u128) * (usd_value as u128) / total) as u64;
+                    (key, asset, share)
+                })
+                .collect();
+            
+            // Hand the rounding remainder to whichever assets still have room
+            let mut leftover = usd_value - draws.iter().map(|(_, _, share)| *share).sum::<u64>();
+            for (_, asset, share) in draws.iter_mut() {
+                if leftover == 0 {
+                    break;
+                }
+                let extra = (asset.usd_value - *share).min(leftover);
+                *share += extra;
+                leftover -= extra;
+            }
+            
+            let mut released = Vec::new();
+            for (key, mut asset, share) in draws {
+                if share == 0 {
+                    continue;
+                }
+                
+                let amount = asset.amount
+                    .checked_mul(share as u128)
+                    .ok_or("Arithmetic overflow")?
+                    / (asset.usd_value as u128);
+                let drawn = asset.clone();
+                asset.amount = asset.amount.checked_sub(amount).ok_or("Arithmetic overflow")?;
+                asset.usd_value = asset.usd_value.checked_sub(share).ok_or("Arithmetic overflow")?;
+                
+                if asset.usd_value == 0 {
+                    self.backing_assets.remove(&key);
+                } else {
+                    self.backing_assets.insert(key, asset);
+                }
+                
+                released.push((drawn, amount, share));
+            }
+            
+            self.total_backing_usd = self.total_backing_usd
+                .checked_sub(usd_value as u128)
+                .ok_or("Arithmetic overflow")?;
+            
+            Ok(released)
+        }
+        
+        /// Payment (by txid) the UTXO verifier has confirmed on this chain and that
+        /// hasn't been credited yet
+        fn verified_payment(&self, txid: [u8; 32], chain: &str) -> Result<UTXOPayment, String> {
+            if self.utxo_verifier.is_empty() {
+                return Err("No UTXO verifier configured".into());
+            }
+            
+            if self.credited_payments.contains_key(&txid) {
+                return Err("Payment already credited".into());
+            }
+            
+            let payment: Option<UTXOPayment> =
+                wasm::call_view(&self.utxo_verifier, "get_payment", &txid)?;
+            let payment = payment.ok_or("Payment not verified")?;
+            
+            if payment.chain != chain {
+                return Err("Payment does not match backing".into());
+            }
+            
+            Ok(payment)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::grail_vault::*;
+    use super::*;
+    use charms_sdk::testing;
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+    
+    const ADMIN: &str = "dao";
+    const MINTER: &str = "minter";
+    const HOLDER: &str = "holder";
+    const VAULT: &str = "grail-vault";
+    const VERIFIER: &str = "utxo-verifier";
+    
+    fn vault() -> GrailVault {
+        testing::reset();
+        testing::set_self_address(VAULT);
+        testing::set_caller(ADMIN);
+        GrailVault::new(ADMIN.into())
+    }
+    
+    /// Link a minter that accepts every burn and refund
+    fn link_minter(vault: &mut GrailVault) {
+        vault.minter = MINTER.into();
+        testing::mock_call::<(String, u64), ()>(MINTER, "vault_burn", |_| Ok(()));
+        testing::mock_call::<(String, u64), ()>(MINTER, "vault_refund", |_| Ok(()));
+    }
+    
+    /// Link a UTXO verifier that confirms every txid as an `amount` payment on `chain`
+    fn link_verifier(vault: &mut GrailVault, chain: &'static str, amount: u64) {
+        vault.utxo_verifier = VERIFIER.into();
+        testing::mock_view::<[u8; 32], Option<UTXOPayment>>(VERIFIER, "get_payment", move |txid| {
+            Ok(Some(UTXOPayment {
+                chain: chain.into(),
+                txid,
+                output_index: 0,
+                amount,
+                recipient: VAULT.into(),
+                confirmations: 6,
+                block_hash: [0u8; 32],
+            }))
+        });
+    }
+    
+    /// Redemption key `HOLDER` registers with the vault
+    fn holder_key() -> Keypair {
+        let secret = SecretKey::from_bytes(&[3u8; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+    
+    /// Credit backing directly, bypassing deposit verification
+    fn seed_asset(vault: &mut GrailVault, chain: &str, token: &str, amount: u128, usd_value: u64) {
+        vault.backing_assets.insert(format!("{}:{}", chain, token), BackingAsset {
+            chain: chain.into(),
+            token_address: token.into(),
+            amount,
+            usd_value,
+        });
+        vault.total_backing_usd += usd_value as u128;
+    }
+    
+    #[test]
+    fn haircut_lowers_solvency() {
+        let mut vault = vault();
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100; // 7_000 USD at the default price
+        
+        assert_eq!(vault.collateral_ratio_bps(), 10_000);
+        assert!(vault.is_solvent());
+        
+        vault.set_haircut("bitcoin:btc".into(), 2_000).unwrap();
+        assert_eq!(vault.effective_backing_usd(), 5_600);
+        assert_eq!(vault.collateral_ratio_bps(), 8_000);
+        assert!(!vault.is_solvent());
+    }
+    
+    #[test]
+    fn haircut_applies_to_collateral_floor() {
+        let mut vault = vault();
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 14_000);
+        vault.zkbtce_supply = 100;
+        vault.min_collateral_bps = 15_000;
+        
+        // 14_000 backing vs 7_000 supply passes the floor at face value...
+        vault.withdraw_backing("bitcoin".into(), "btc".into(), 10, 140, "ops".into()).unwrap();
+        
+        // ...but not once half of it is discounted
+        vault.set_haircut("bitcoin:btc".into(), 5_000).unwrap();
+        assert_eq!(
+            vault.withdraw_backing("bitcoin".into(), "btc".into(), 10, 140, "ops".into()),
+            Err("Would breach collateral floor".into())
+        );
+    }
+    
+    #[test]
+    fn same_chain_redemption_releases_directly() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(vault.redeem_to_chain(10, "bitcoin".into(), "bc1q".into()), Ok(true));
+        assert_eq!(vault.backing_assets.get("bitcoin:btc").unwrap().usd_value, 6_300);
+        assert!(vault.redemption_queue.is_empty());
+    }
+    
+    #[test]
+    fn cross_chain_or_paused_redemption_is_queued() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        vault.pause_asset("cardano:ada".into()).unwrap();
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(vault.redeem_to_chain(10, "litecoin".into(), "ltc1q".into()), Ok(false));
+        assert_eq!(vault.redeem_to_chain(10, "cardano".into(), "addr1".into()), Ok(false));
+        assert_eq!(vault.redemption_queue.len(), 2);
+    }
+    
+    #[test]
+    fn direct_release_respects_collateral_floor() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_700);
+        vault.zkbtce_supply = 100;
+        vault.min_collateral_bps = 11_000;
+        vault.set_haircut("bitcoin:btc".into(), 1_000).unwrap(); // 6_930 effective, under the floor
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.redeem_to_chain(10, "bitcoin".into(), "bc1q".into()),
+            Err("Would breach collateral floor".into())
+        );
+    }
+    
+    #[test]
+    fn vault_cannot_register_itself_as_sibling() {
+        let mut vault = vault();
+        
+        assert_eq!(
+            vault.register_sibling_vault(VAULT.into()),
+            Err("Vault cannot be its own sibling".into())
+        );
+        assert!(vault.register_sibling_vault("grail-vault-2".into()).is_ok());
+        assert_eq!(vault.sibling_vaults, vec!["grail-vault-2".to_string()]);
+    }
+    
+    #[test]
+    fn aggregate_reserves_apply_haircuts_and_skip_unreachable_siblings() {
+        let mut vault = vault();
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.set_haircut("bitcoin:btc".into(), 2_000).unwrap(); // 5_600 effective
+        vault.zkbtce_supply = 100;
+        vault.register_sibling_vault("grail-vault-eu".into()).unwrap();
+        vault.register_sibling_vault("grail-vault-asia".into()).unwrap();
+        
+        // Only the EU sibling answers; the Asian one has no view mocked
+        testing::mock_view::<(), ReserveSnapshot>("grail-vault-eu", "reserve_snapshot", |_| {
+            Ok(ReserveSnapshot {
+                total_backing_usd: 7_000,
+                effective_backing_usd: 7_000,
+                reserved_backing_usd: 0,
+                zkbtce_supply: 100,
+                taken_at: 0,
+            })
+        });
+        
+        let aggregate = vault.aggregate_reserves();
+        assert_eq!(aggregate.total_backing_usd, 14_000);
+        assert_eq!(aggregate.effective_backing_usd, 12_600);
+        assert_eq!(aggregate.zkbtce_supply, 200);
+        assert_eq!(aggregate.vaults_counted, 2);
+        assert_eq!(aggregate.unreachable, vec!["grail-vault-asia".to_string()]);
+        assert!(aggregate.is_partial());
+        
+        // 12_600 haircut backing against 14_000 of supply, not the face-value 100%
+        assert_eq!(aggregate.collateral_ratio_bps, 9_000);
+        assert_eq!(vault.collateral_ratio_bps(), 8_000);
+    }
+    
+    #[test]
+    fn rebalance_suggestions_track_target_weights() {
+        let mut vault = vault();
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 8_000);
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 2_000);
+        vault.set_target_weight("bitcoin:btc".into(), 5_000).unwrap();
+        vault.set_target_weight("cardano:ada".into(), 5_000).unwrap();
+        
+        assert_eq!(
+            vault.rebalance_suggestions(),
+            vec![("bitcoin:btc".to_string(), -3_000), ("cardano:ada".to_string(), 3_000)]
+        );
+        
+        vault.record_rebalance(
+            "bitcoin:btc".into(), 375, 3_000,
+            "cardano".into(), "ada".into(), 1_500, 3_000,
+        ).unwrap();
+        assert!(vault.rebalance_suggestions().is_empty());
+    }
+    
+    #[test]
+    fn rebalance_keeps_asset_marked_to_zero_while_holding_tokens() {
+        let mut vault = vault();
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 8_000);
+        
+        vault.record_rebalance(
+            "bitcoin:btc".into(), 500, 8_000,
+            "cardano".into(), "ada".into(), 4_000, 8_000,
+        ).unwrap();
+        let btc = vault.backing_assets.get("bitcoin:btc").unwrap();
+        assert_eq!((btc.amount, btc.usd_value), (500, 0));
+        
+        vault.record_rebalance(
+            "bitcoin:btc".into(), 500, 0,
+            "cardano".into(), "ada".into(), 0, 0,
+        ).unwrap();
+        assert!(vault.backing_assets.get("bitcoin:btc").is_none());
+    }
+    
+    #[test]
+    fn queue_events_carry_request_ids() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        testing::set_caller(HOLDER);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-a".into()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-b".into()).unwrap();
+        let enqueued = testing::emitted_events::<RedemptionEnqueuedEvent>("RedemptionEnqueued");
+        assert_eq!(enqueued.iter().map(|e| e.id).collect::<Vec<_>>(), vec![0, 1]);
+        
+        testing::set_caller(ADMIN);
+        vault.process_redemptions(1).unwrap();
+        let dequeued = testing::emitted_events::<RedemptionDequeuedEvent>("RedemptionDequeued");
+        assert_eq!(dequeued.len(), 1);
+        assert_eq!((dequeued[0].id, dequeued[0].recipient.as_str()), (0, "bc1q-a"));
+    }
+    
+    #[test]
+    fn batch_pays_each_request_on_its_own_chain() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        testing::set_caller(HOLDER);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-a".into()).unwrap();
+        vault.request_redemption(10, "cardano".into(), "addr1-b".into()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-c".into()).unwrap();
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(vault.process_redemptions(10).map(|settled| settled.len()), Ok(3));
+        
+        let payouts = testing::emitted_events::<ChainPayoutEvent>("ChainPayout");
+        assert_eq!(payouts.len(), 2);
+        let recipients = |payout: &ChainPayoutEvent| -> Vec<String> {
+            payout.items.iter().map(|(recipient, ..)| recipient.clone()).collect()
+        };
+        assert_eq!((payouts[0].chain.as_str(), payouts[0].total_usd), ("bitcoin", 1_400));
+        assert_eq!(recipients(&payouts[0]), vec!["bc1q-a".to_string(), "bc1q-c".to_string()]);
+        assert_eq!((payouts[1].chain.as_str(), payouts[1].total_usd), ("cardano", 700));
+        assert_eq!(recipients(&payouts[1]), vec!["addr1-b".to_string()]);
+        
+        assert_eq!(vault.backing_assets.get("bitcoin:btc").unwrap().usd_value, 5_600);
+        assert_eq!(vault.backing_assets.get("cardano:ada").unwrap().usd_value, 6_300);
+    }
+    
+    #[test]
+    fn short_chain_holds_back_the_rest_of_the_queue() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        testing::set_caller(HOLDER);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-a".into()).unwrap();
+        vault.request_redemption(10, "litecoin".into(), "ltc1q".into()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-c".into()).unwrap();
+        
+        // Nothing is held on litecoin yet, so it and everything behind it waits
+        testing::set_caller(ADMIN);
+        assert_eq!(vault.process_redemptions(10), Ok(vec![("bc1q-a".to_string(), 10)]));
+        assert_eq!(vault.redemption_queue.len(), 2);
+        assert_eq!(vault.redemption_queue[0].dest_chain, "litecoin");
+    }
+    
+    #[test]
+    fn cancellation_emits_dequeued() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        let key = holder_key();
+        
+        testing::set_caller(HOLDER);
+        vault.set_redemption_key(key.public.to_bytes()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q".into()).unwrap();
+        
+        let auth = key.sign(&cancellation_auth_bytes(VAULT, HOLDER, 0, 0)).to_bytes();
+        vault.cancel_redemption(0, auth).unwrap();
+        
+        let dequeued = testing::emitted_events::<RedemptionDequeuedEvent>("RedemptionDequeued");
+        assert_eq!(dequeued.len(), 1);
+        assert_eq!((dequeued[0].id, dequeued[0].queue_depth), (0, 0));
+        assert_eq!(vault.zkbtce_supply, 100);
+        assert_eq!(vault.reserved_backing_usd, 0);
+    }
+    
+    #[test]
+    fn authorizations_do_not_replay_across_vaults() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        let key = holder_key();
+        
+        testing::set_caller(HOLDER);
+        vault.set_redemption_key(key.public.to_bytes()).unwrap();
+        
+        // Signed for a sibling vault sharing the same key and nonce
+        let foreign = key.sign(&redemption_auth_bytes("grail-vault-2", HOLDER, 10, "bitcoin", "bc1q", 0)).to_bytes();
+        testing::set_caller("custodian");
+        assert_eq!(
+            vault.request_redemptions_batch(vec![(HOLDER.into(), 10, "bitcoin".into(), "bc1q".into(), foreign)]),
+            Err("Invalid redemption authorization".into())
+        );
+        
+        let own = key.sign(&redemption_auth_bytes(VAULT, HOLDER, 10, "bitcoin", "bc1q", 0)).to_bytes();
+        assert_eq!(
+            vault.request_redemptions_batch(vec![(HOLDER.into(), 10, "bitcoin".into(), "bc1q".into(), own)]),
+            Ok(1)
+        );
+    }
+    
+    #[test]
+    fn only_minter_records_mints() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(vault.record_mint(10), Err("Caller is not minter".into()));
+        
+        testing::set_caller(MINTER);
+        vault.record_mint(10).unwrap();
+        assert_eq!(vault.zkbtce_supply, 10);
+    }
+    
+    #[test]
+    fn minted_supply_can_be_redeemed_through_the_queue() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        
+        testing::set_caller(MINTER);
+        vault.record_mint(100).unwrap();
+        
+        testing::set_caller(HOLDER);
+        vault.request_redemption(40, "bitcoin".into(), "bc1q".into()).unwrap();
+        assert_eq!(vault.zkbtce_supply, 60);
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(vault.process_redemptions(10), Ok(vec![("bc1q".to_string(), 40)]));
+        assert!(vault.redemption_queue.is_empty());
+        assert_eq!(vault.total_backing_usd, 4_200);
+    }
+    
+    #[test]
+    fn growth_cap_limits_mint_capacity_from_the_first_window() {
+        let mut vault = vault();
+        testing::set_block_timestamp(1_000);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.set_backing_growth_cap(1_000, 86_400).unwrap();
+        assert_eq!(vault.growth_window_base, 7_000);
+        assert_eq!(vault.mint_capacity(), 100);
+        
+        // Backing doubles, but only 10% of the base counts this window
+        seed_asset(&mut vault, "cardano", "ada", 1_000, 7_000);
+        assert_eq!(vault.mint_eligible_backing_usd(), 7_700);
+        assert_eq!(vault.mint_capacity(), 110);
+        
+        vault.zkbtce_supply = 110;
+        assert_eq!(vault.mint_capacity(), 0);
+    }
+    
+    #[test]
+    fn oversized_holdings_error_instead_of_overflowing() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", u128::MAX, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.redeem_to_chain(10, "bitcoin".into(), "bc1q".into()),
+            Err("Arithmetic overflow".into())
+        );
+    }
+    
+    #[test]
+    #[should_panic(expected = "Admin must not be empty")]
+    fn empty_admin_is_rejected() {
+        testing::reset();
+        GrailVault::new(String::new());
+    }
+    
+    #[test]
+    fn admin_gated_messages_reject_other_callers() {
+        let mut vault = vault();
+        vault.utxo_verifier = VERIFIER.into();
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.add_backing("bitcoin".into(), "btc".into(), 1_000, 7_000, [1u8; 32], None),
+            Err("Caller is not admin".into())
+        );
+        assert_eq!(vault.set_haircut("bitcoin:btc".into(), 2_000), Err("Caller is not admin".into()));
+        assert_eq!(vault.process_redemptions(10), Err("Caller is not admin".into()));
+        assert_eq!(vault.propose_admin(HOLDER.into()), Err("Caller is not admin".into()));
+        assert!(vault.backing_assets.get("bitcoin:btc").is_none());
+    }
+    
+    #[test]
+    fn backing_at_the_minimum_is_credited_and_below_it_rejected() {
+        let mut vault = vault();
+        link_verifier(&mut vault, "bitcoin", 1_000);
+        vault.set_min_backing_usd(500).unwrap();
+        
+        assert_eq!(
+            vault.add_backing("bitcoin".into(), "btc".into(), 1_000, 499, [1u8; 32], None),
+            Err("Backing below minimum".into())
+        );
+        // The floor applies to the value credited after the payer's cap
+        assert_eq!(
+            vault.add_backing("bitcoin".into(), "btc".into(), 1_000, 700, [1u8; 32], Some(400)),
+            Err("Backing below minimum".into())
+        );
+        assert!(vault.backing_assets.get("bitcoin:btc").is_none());
+        
+        vault.add_backing("bitcoin".into(), "btc".into(), 1_000, 500, [1u8; 32], None).unwrap();
+        assert_eq!(vault.backing_assets.get("bitcoin:btc").unwrap().usd_value, 500);
+        assert_eq!(vault.total_backing_usd, 500);
+    }
+    
+    /// Vault with one request queued at t=1_000 and one at t=1_150, expiring after 100s
+    fn vault_with_aging_queue(policy: ExpiryPolicy) -> GrailVault {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        vault.set_queue_expiry(100, policy).unwrap();
+        
+        testing::set_caller(HOLDER);
+        testing::set_block_timestamp(1_000);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-stale".into()).unwrap();
+        testing::set_block_timestamp(1_150);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-fresh".into()).unwrap();
+        
+        testing::set_block_timestamp(1_200);
+        vault
+    }
+    
+    #[test]
+    fn stale_redemption_expires_and_fresh_one_stays() {
+        let mut vault = vault_with_aging_queue(ExpiryPolicy::Refund);
+        
+        assert_eq!(vault.expire_redemptions(1_200), Ok(1));
+        assert_eq!(vault.redemption_queue.len(), 1);
+        assert_eq!(vault.redemption_queue[0].recipient, "bc1q-fresh");
+        
+        // The refund returns the stale request's tokens and frees its backing
+        assert_eq!(vault.zkbtce_supply, 90);
+        assert_eq!(vault.reserved_backing_usd, 700);
+        
+        let expired = testing::emitted_events::<RedemptionExpiredEvent>("RedemptionExpired");
+        assert_eq!(expired.len(), 1);
+        assert_eq!((expired[0].recipient.as_str(), expired[0].enqueued_at, expired[0].refunded), ("bc1q-stale", 1_000, true));
+    }
+    
+    #[test]
+    fn forfeited_redemption_keeps_its_tokens_burned() {
+        let mut vault = vault_with_aging_queue(ExpiryPolicy::Forfeit);
+        
+        assert_eq!(vault.expire_redemptions(1_200), Ok(1));
+        assert_eq!(vault.zkbtce_supply, 80);
+        assert_eq!(vault.reserved_backing_usd, 700);
+        
+        let expired = testing::emitted_events::<RedemptionExpiredEvent>("RedemptionExpired");
+        assert!(!expired[0].refunded);
+    }
+    
+    #[test]
+    fn expiry_rejects_a_future_timestamp() {
+        let mut vault = vault_with_aging_queue(ExpiryPolicy::Refund);
+        
+        assert_eq!(vault.expire_redemptions(5_000), Err("Timestamp in the future".into()));
+        assert_eq!(vault.redemption_queue.len(), 2);
+    }
+    
+    #[test]
+    fn zero_redemptions_and_deposits_are_rejected() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        link_verifier(&mut vault, "bitcoin", 1_000);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        
+        for (amount, usd_value) in [(0, 7_000), (1_000, 0)] {
+            assert_eq!(
+                vault.add_backing("bitcoin".into(), "btc".into(), amount, usd_value, [1u8; 32], None),
+                Err("Zero backing amount".into())
+            );
+        }
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.request_redemption(0, "bitcoin".into(), "bc1q".into()),
+            Err("Zero redemption amount".into())
+        );
+        assert_eq!(
+            vault.redeem_to_chain(0, "bitcoin".into(), "bc1q".into()),
+            Err("Zero redemption amount".into())
+        );
+        assert!(vault.redemption_queue.is_empty());
+        assert_eq!((vault.zkbtce_supply, vault.total_backing_usd), (100, 7_000));
+    }
+    
+    #[test]
+    fn backing_near_the_limits_errors_instead_of_wrapping() {
+        let credit = |vault: &mut GrailVault| {
+            vault.add_backing("bitcoin".into(), "btc".into(), 1_000, 500, [1u8; 32], None)
+        };
+        
+        // Token amount, asset USD value and vault-wide USD total in turn
+        let mut by_amount = vault();
+        link_verifier(&mut by_amount, "bitcoin", 1_000);
+        seed_asset(&mut by_amount, "bitcoin", "btc", u128::MAX - 10, 7_000);
+        assert_eq!(credit(&mut by_amount), Err("Backing overflow".into()));
+        assert_eq!(by_amount.backing_assets.get("bitcoin:btc").unwrap().amount, u128::MAX - 10);
+        
+        let mut by_asset_usd = vault();
+        link_verifier(&mut by_asset_usd, "bitcoin", 1_000);
+        seed_asset(&mut by_asset_usd, "bitcoin", "btc", 1_000, u64::MAX - 100);
+        assert_eq!(credit(&mut by_asset_usd), Err("Backing overflow".into()));
+        assert_eq!(by_asset_usd.backing_assets.get("bitcoin:btc").unwrap().usd_value, u64::MAX - 100);
+        
+        let mut by_total_usd = vault();
+        link_verifier(&mut by_total_usd, "bitcoin", 1_000);
+        by_total_usd.total_backing_usd = u128::MAX - 100;
+        assert_eq!(credit(&mut by_total_usd), Err("Backing overflow".into()));
+        assert!(by_total_usd.backing_assets.get("bitcoin:btc").is_none());
+        assert_eq!(by_total_usd.total_backing_usd, u128::MAX - 100);
+        assert!(by_total_usd.credited_payments.get(&[1u8; 32]).is_none());
+    }
+    
+    #[test]
+    fn owner_redirects_a_queued_redemption_but_not_a_processed_one() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        let key = holder_key();
+        
+        testing::set_caller(HOLDER);
+        vault.set_redemption_key(key.public.to_bytes()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-lost".into()).unwrap();
+        vault.request_redemption(10, "bitcoin".into(), "bc1q-b".into()).unwrap();
+        
+        // Anyone may relay the owner's signed reassignment
+        let auth = key.sign(&reassignment_auth_bytes(VAULT, HOLDER, 0, "bc1q-lost", "bc1q-new", 0)).to_bytes();
+        testing::set_caller("relayer");
+        vault.reassign_redemption(0, "bc1q-new".into(), auth).unwrap();
+        assert_eq!(vault.redemption_queue[0].recipient, "bc1q-new");
+        let reassigned = testing::emitted_events::<RedemptionReassignedEvent>("RedemptionReassigned");
+        assert_eq!(reassigned.len(), 1);
+        assert_eq!((reassigned[0].id, reassigned[0].old_recipient.as_str()), (0, "bc1q-lost"));
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(vault.process_redemptions(1), Ok(vec![("bc1q-new".to_string(), 10)]));
+        
+        // Request 0 has left the queue, so its index now holds request 1
+        let late = key.sign(&reassignment_auth_bytes(VAULT, HOLDER, 0, "bc1q-new", "bc1q-other", 1)).to_bytes();
+        assert_eq!(
+            vault.reassign_redemption(0, "bc1q-other".into(), late),
+            Err("Invalid reassignment authorization".into())
+        );
+        assert_eq!(
+            vault.reassign_redemption(1, "bc1q-other".into(), late),
+            Err("No queued redemption at index".into())
+        );
+        assert_eq!(vault.redemption_queue[0].recipient, "bc1q-b");
+    }
+    
+    #[test]
+    fn redemption_waits_for_a_price() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        vault.zkbtce_supply = 100;
+        // State carried over from before the price was configured
+        vault.price_per_token_usd = 0;
+        
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.request_redemption(10, "bitcoin".into(), "bc1q".into()),
+            Err("Price not set".into())
+        );
+        assert!(vault.redemption_queue.is_empty());
+        assert_eq!(vault.zkbtce_supply, 100);
+        
+        testing::set_caller(ADMIN);
+        vault.set_price_per_token(70).unwrap();
+        testing::set_caller(HOLDER);
+        vault.request_redemption(10, "bitcoin".into(), "bc1q".into()).unwrap();
+        assert_eq!(vault.redemption_queue[0].usd_value, 700);
+        assert_eq!((vault.zkbtce_supply, vault.reserved_backing_usd), (90, 700));
+    }
+    
+    #[test]
+    fn supply_near_the_limits_errors_instead_of_wrapping() {
+        let mut vault = vault();
+        link_minter(&mut vault);
+        seed_asset(&mut vault, "bitcoin", "btc", 1_000, 7_000);
+        
+        testing::set_caller(MINTER);
+        vault.record_mint(u64::MAX - 5).unwrap();
+        assert_eq!(vault.record_mint(10), Err("Arithmetic overflow".into()));
+        assert_eq!(vault.zkbtce_supply, u64::MAX - 5);
+        
+        // Valued at the live price, the redemption would exceed u64 USD
+        testing::set_caller(HOLDER);
+        assert_eq!(
+            vault.request_redemption(u64::MAX / 2, "bitcoin".into(), "bc1q".into()),
+            Err("Arithmetic overflow".into())
+        );
+        
+        // Redeeming more than was ever minted fails instead of underflowing supply
+        vault.zkbtce_supply = 10;
+        assert_eq!(
+            vault.request_redemption(20, "bitcoin".into(), "bc1q".into()),
+            Err("Arithmetic overflow".into())
+        );
+        assert_eq!((vault.zkbtce_supply, vault.reserved_backing_usd), (10, 0));
+        assert!(vault.redemption_queue.is_empty());
     }
 }
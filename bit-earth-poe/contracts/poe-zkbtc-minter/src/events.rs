@@ -0,0 +1,296 @@
+//! Event payloads emitted by the minter
+//! Every `emit_event` passes one of these structs; bump `EVENT_SCHEMA_VERSION`
+//! whenever a field is added, removed or reordered.
+
+use charms_sdk::prelude::*;
+
+use crate::{
+    AdminAction, BridgeInstruction, DeviceStatus, EnergySource, EnergyUnit, MintRecord, OracleRole,
+};
+
+/// Layout version carried by every event payload
+pub const EVENT_SCHEMA_VERSION: u16 = 2;
+
+/// `DeviceRegistered`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceRegisteredEvent {
+    pub device_id: [u8; 32],
+    pub wallet: String,
+    pub region: u16,
+    pub unit: EnergyUnit,
+    pub schema_version: u16,
+}
+
+/// `DeviceStatusChanged`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceStatusChangedEvent {
+    pub device_id: [u8; 32],
+    pub from: DeviceStatus,
+    pub to: DeviceStatus,
+    pub schema_version: u16,
+}
+
+/// `DevicePaused` / `DeviceUnpaused`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DevicePauseEvent {
+    pub device_id: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `DeviceVestingUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceVestingUpdatedEvent {
+    pub device_id: [u8; 32],
+    pub bps: u16,
+    pub period: u64,
+    pub schema_version: u16,
+}
+
+/// `DeviceFirmwareUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceFirmwareUpdatedEvent {
+    pub device_id: [u8; 32],
+    pub firmware_version: u32,
+    pub schema_version: u16,
+}
+
+/// `FirmwareBlockUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareBlockUpdatedEvent {
+    pub firmware_version: u32,
+    pub blocked: bool,
+    pub schema_version: u16,
+}
+
+/// `DeviceReclassified`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceReclassifiedEvent {
+    pub device_id: [u8; 32],
+    pub previous_region: u16,
+    pub region: u16,
+    pub version: u32,
+    pub schema_version: u16,
+}
+
+/// `CumulativeReset`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct CumulativeResetEvent {
+    pub device_id: [u8; 32],
+    pub previous: u128,
+    pub new_anchor: u128,
+    pub evidence: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `PoEMinted`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoEMintedEvent {
+    pub device_id: [u8; 32],
+    pub total: u64,
+    pub prosumer: u64,
+    pub protocol: u64,
+    pub energy_source: EnergySource,
+    pub grid_id: u32,
+    pub schema_version: u16,
+}
+
+/// `BatchMinted`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchMintedEvent {
+    pub batch_id: [u8; 32],
+    /// Items processed so far, across calls
+    pub processed: u32,
+    pub total_items: u32,
+    /// Outcomes of this call only
+    pub succeeded: u32,
+    /// Items from attest-only oracles, recorded without minting
+    pub attested: u32,
+    pub failed: u32,
+    pub tokens_minted: u64,
+    pub schema_version: u16,
+}
+
+/// `MintArchived`: a mint record pruned from the on-chain mint log
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintArchivedEvent {
+    pub record: MintRecord,
+    pub schema_version: u16,
+}
+
+/// `EnergyAttested`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnergyAttestedEvent {
+    pub device_id: [u8; 32],
+    pub oracle_id: [u8; 32],
+    pub energy_wh: u64,
+    pub cumulative_energy: u128,
+    pub schema_version: u16,
+}
+
+/// `ProtocolFeesDistributed`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolFeesDistributedEvent {
+    /// (recipient, tokens)
+    pub payouts: Vec<(String, u64)>,
+    pub schema_version: u16,
+}
+
+/// `MintFlagged`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintFlaggedEvent {
+    pub mint_id: [u8; 32],
+    pub txid: [u8; 32],
+    pub reason: String,
+    pub schema_version: u16,
+}
+
+/// `VestedClaimed`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestedClaimedEvent {
+    pub wallet: String,
+    pub amount: u64,
+    pub schema_version: u16,
+}
+
+/// `AssetsBridged`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetsBridgedEvent {
+    pub instruction: BridgeInstruction,
+    pub schema_version: u16,
+}
+
+/// `BridgeFeeUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BridgeFeeUpdatedEvent {
+    pub chain: String,
+    pub fee_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `PriceUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceUpdatedEvent {
+    pub price_per_token_usd: u64,
+    pub schema_version: u16,
+}
+
+/// `RegionRatioUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionRatioUpdatedEvent {
+    pub region: u16,
+    pub wh_per_token: u64,
+    pub schema_version: u16,
+}
+
+/// `EmergencyOracleAdded`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmergencyOracleAddedEvent {
+    pub oracle_id: [u8; 32],
+    pub added_by: String,
+    pub expires_at: u64,
+    pub schema_version: u16,
+}
+
+/// `EmergencyOracleRatified`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmergencyOracleRatifiedEvent {
+    pub oracle_id: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `OracleRoleChanged`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleRoleChangedEvent {
+    pub oracle_id: [u8; 32],
+    pub role: OracleRole,
+    pub schema_version: u16,
+}
+
+/// `OraclesImported`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OraclesImportedEvent {
+    pub imported: u32,
+    pub schema_version: u16,
+}
+
+/// `OracleThresholdUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleThresholdUpdatedEvent {
+    pub threshold: u32,
+    pub schema_version: u16,
+}
+
+/// `OracleGroupKeyUpdated`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleGroupKeyUpdatedEvent {
+    pub group_key: [u8; 32],
+    pub threshold: u32,
+    pub schema_version: u16,
+}
+
+/// `VerifyingKeyRegistered`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerifyingKeyRegisteredEvent {
+    pub vk_hash: [u8; 32],
+    pub schema_version: u16,
+}
+
+/// `Paused` / `Unpaused`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseEvent {
+    pub caller: String,
+    pub schema_version: u16,
+}
+
+/// `AdminTransferStarted`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminTransferStartedEvent {
+    pub admin: String,
+    pub pending_admin: String,
+    pub schema_version: u16,
+}
+
+/// `AdminTransferCompleted`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminTransferCompletedEvent {
+    pub previous_admin: String,
+    pub admin: String,
+    pub schema_version: u16,
+}
+
+/// `AdminAction`
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminActionEvent {
+    pub entry: AdminAction,
+    pub schema_version: u16,
+}
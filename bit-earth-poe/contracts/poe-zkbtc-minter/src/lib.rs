@@ -10,6 +10,86 @@ use charms_sdk::{
     wasm::{self, *},
     storage::{Map, Vec as StorageVec},
 };
+use bellman::groth16::{prepare_verifying_key, verify_proof, Proof, VerifyingKey};
+use bls12_381::{Bls12, Scalar};
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+
+pub mod events;
+pub use events::*;
+
+// Public-input layout of the PoE circuit, shared with `poe-circuit`: the circuit
+// allocates its inputs in index order, so these positions are the only source
+// of truth for both sides.
+/// Position of the energy (Wh) in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_ENERGY: usize = 0;
+/// Position of the device id digest in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_DEVICE: usize = 1;
+/// Position of the recent block hash anchor (its `public_input_digest`) in
+/// `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_ANCHOR: usize = 2;
+/// Position of the prover's current time (ms) in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_CURRENT_TIME: usize = 3;
+/// Position of the device classification digest in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_CLASSIFICATION: usize = 4;
+/// Position of the reading's timestamp (ms) in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_TIMESTAMP: usize = 5;
+/// Position of the freshness window (ms) in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_MAX_AGE: usize = 6;
+/// Position of the oracle-signature validity flag in `ZkMintProof::public_inputs`
+pub const PUBLIC_INPUT_ORACLE_VALID: usize = 7;
+/// Number of public inputs a mint proof carries, exactly
+pub const POE_PUBLIC_INPUT_COUNT: usize = 8;
+
+/// Default maximum age of a proof's block anchor, in blocks (~1 day)
+pub const DEFAULT_MAX_ANCHOR_DEPTH: u64 = 144;
+
+/// Default allowed drift between a proof's current time and block time (ms)
+pub const DEFAULT_PROOF_TIME_TOLERANCE_MS: u64 = 10 * 60 * 1_000;
+
+/// Default maximum packet age a proof's circuit must enforce (ms)
+pub const DEFAULT_MAX_PROOF_AGE_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Default energy per zkBTC-E (1 MWh)
+pub const WH_PER_TOKEN: u64 = 1_000_000;
+
+/// Initial USD backing per zkBTC-E
+pub const TOKEN_PRICE_USD: u64 = 70;
+
+/// Layout version of `BridgeInstruction`
+pub const BRIDGE_INSTRUCTION_VERSION: u16 = 2;
+
+/// Default time relayers have to deliver a bridged burn (seconds)
+pub const DEFAULT_BRIDGE_DEADLINE_SECS: u64 = 86_400;
+
+/// Default spacing between device energy snapshots (seconds)
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 86_400;
+
+/// Snapshots retained per device before the oldest is dropped
+pub const MAX_SNAPSHOTS_PER_DEVICE: usize = 365;
+
+/// Basis-point denominator (100%)
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Depth of the sparse Merkle tree of spent nullifiers (one level per key bit)
+pub const NULLIFIER_TREE_DEPTH: usize = 256;
+
+/// Default number of verified proofs kept in the verification cache
+pub const DEFAULT_PROOF_CACHE_CAPACITY: u32 = 1_024;
+
+/// Default lifetime of an unratified emergency oracle (seconds)
+pub const DEFAULT_EMERGENCY_ORACLE_TTL: u64 = 72 * 3_600;
+
+/// Most items a single `mint_batch` call processes
+pub const MAX_BATCH_ITEMS: u32 = 32;
+
+/// Maximum number of items a single batch may carry across all calls
+pub const MAX_BATCH_LEN: usize = 256;
+
+/// Verification attempts after which all stats counters are halved
+pub const VERIFICATION_STATS_WINDOW: u64 = 10_000;
+
+/// Distinct rejection reasons tracked before the rest are lumped together
+pub const MAX_REJECTION_REASONS: usize = 16;
 
 /// Device certification status
 #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
@@ -21,6 +101,131 @@ pub enum DeviceStatus {
     Decommissioned,
 }
 
+/// Inconsistency between a device's status and its per-device state
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntegrityIssue {
+    /// Certified, but mints would fail with "No wallet mapped to device"
+    CertifiedWithoutWallet,
+    /// Wallet mapped to a device that was never registered
+    WalletWithoutRegistration,
+    /// Registered without an energy unit, so mints can't normalize readings
+    MissingEnergyUnit,
+    /// Registered without a region
+    MissingRegion,
+    /// Certified without a lifetime ceiling (e.g. certified before ceilings existed)
+    MissingLifetimeCeiling,
+    /// Pause flag set on a device that was never registered
+    PausedWithoutRegistration,
+    /// Minting history on a device that was never registered
+    EnergyWithoutRegistration,
+}
+
+/// Unit a device's meter reports energy in
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum EnergyUnit {
+    Wh,
+    KWh,
+    /// Megajoules (1 MJ = 277.78 Wh)
+    MJ,
+}
+
+impl EnergyUnit {
+    /// Convert a reading in this unit to whole Wh (rounding down)
+    pub fn to_wh(self, value: u128) -> Option<u128> {
+        match self {
+            EnergyUnit::Wh => Some(value),
+            EnergyUnit::KWh => value.checked_mul(1_000),
+            // 1 MJ = 1_000_000 J / 3_600 J/Wh = 2_500/9 Wh
+            EnergyUnit::MJ => value.checked_mul(2_500).map(|joules| joules / 9),
+        }
+    }
+}
+
+/// What a whitelisted oracle's packets may be used for
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum OracleRole {
+    /// Packets can mint zkBTC-E
+    MintAuthorized,
+    /// Packets are recorded for analytics only (e.g. during an investigation)
+    AttestOnly,
+}
+
+/// Network the contract is deployed on
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+/// How mint and burn proofs are checked
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerificationMode {
+    /// Full Groth16 verification
+    Full,
+    /// Skip the pairing check (test networks only); replay and device checks still apply
+    TrustedSkip,
+}
+
+/// Why `verify_proof_only` rejected a proof, by the stage that failed. Each
+/// variant carries the message the mint path returns for the same failure.
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum MinterError {
+    /// The proof's nullifier is already spent
+    ProofAlreadyUsed,
+    /// Groth16 check or binding to the packet failed
+    InvalidProof(String),
+    /// The anchor isn't a recent enough block hash
+    StaleAnchor(String),
+    /// Proof time or max age is outside the minter's policy
+    ProofTime(String),
+    /// The proof commits to an outdated or wrong device classification
+    Classification(String),
+}
+
+impl core::fmt::Display for MinterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MinterError::ProofAlreadyUsed => f.write_str("Proof already used"),
+            MinterError::InvalidProof(reason)
+            | MinterError::StaleAnchor(reason)
+            | MinterError::ProofTime(reason)
+            | MinterError::Classification(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl From<MinterError> for String {
+    fn from(error: MinterError) -> String {
+        error.to_string()
+    }
+}
+
+/// Portable description of a whitelisted oracle, used to migrate the whitelist
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleInfo {
+    pub role: OracleRole,
+}
+
+/// Generation technology behind a reading, attested by the oracle
+#[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum EnergySource {
+    Solar,
+    Wind,
+    Hydro,
+    Geothermal,
+    Biomass,
+    Other,
+}
+
 /// Proof-of-Energy data packet from IoT sensor
 #[derive(Debug, Clone, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -33,12 +238,72 @@ pub struct PoEPacket {
     pub energy_wh: u64,
     /// Cumulative energy counter (prevents replay)
     pub cumulative_energy: u128,
+    /// Generation technology (oracle-attested)
+    pub energy_source: EnergySource,
+    /// Coarse location / grid zone id (oracle-attested)
+    pub grid_id: u32,
     /// Digital signature (Ed25519)
     pub signature: [u8; 64],
     /// Oracle node ID that verified this
     pub oracle_id: [u8; 32],
 }
 
+/// Canonical bytes an oracle signs for a packet. The oracle service signs
+/// exactly these bytes, so any change here must ship to both sides.
+pub fn packet_signing_bytes(packet: &PoEPacket) -> Vec<u8> {
+    let mut message = packet.device_id.to_vec();
+    message.extend_from_slice(&packet.timestamp.to_be_bytes());
+    message.extend_from_slice(&packet.energy_wh.to_be_bytes());
+    message.extend_from_slice(&packet.cumulative_energy.to_be_bytes());
+    message.push(packet.energy_source as u8);
+    message.extend_from_slice(&packet.grid_id.to_be_bytes());
+    message
+}
+
+/// Bytes the oracle group signs for a threshold-signed mint: the packet bound to
+/// the `threshold` its key was generated for, so a share set below the registered
+/// M can't sign for it
+pub fn group_signing_bytes(packet: &PoEPacket, threshold: u32) -> Vec<u8> {
+    let mut message = b"poe-minter:group".to_vec();
+    message.extend_from_slice(&threshold.to_be_bytes());
+    message.extend_from_slice(&packet_signing_bytes(packet));
+    message
+}
+
+/// Public inputs travel as the 32-byte big-endian encoding of a BLS12-381
+/// scalar; the PoE circuit decodes them with the same byte order.
+/// Digests keep 254 bits so they always fit below the scalar modulus.
+pub fn public_input_digest(data: &[u8]) -> [u8; 32] {
+    let mut digest = sha256(data);
+    digest[0] &= 0x3f;
+    digest
+}
+
+/// Public input binding a proof to a device's region as of classification
+/// `version`; reclassifying the device invalidates proofs made before it
+pub fn classification_digest(region: u16, version: u32) -> [u8; 32] {
+    let mut data = region.to_be_bytes().to_vec();
+    data.extend_from_slice(&version.to_be_bytes());
+    public_input_digest(&data)
+}
+
+/// Canonical public-input encoding of an integer (big-endian, left-padded)
+pub fn public_input_from_u64(value: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+/// Decode a public input holding an integer; `None` if it doesn't fit in a u64
+pub fn public_input_to_u64(input: &[u8; 32]) -> Option<u64> {
+    if input[..24].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&input[24..]);
+    Some(u64::from_be_bytes(bytes))
+}
+
 /// Zero-knowledge minting proof
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct ZkMintProof {
@@ -50,6 +315,157 @@ pub struct ZkMintProof {
     pub vk_hash: [u8; 32],
 }
 
+/// Everything needed to mint one reading, bundled by an aggregating oracle or relayer
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Attestation {
+    pub packet: PoEPacket,
+    pub zk_proof: ZkMintProof,
+    /// (oracle_id, signature over `packet_signing_bytes`) per co-signing oracle
+    pub signatures: Vec<([u8; 32], [u8; 64])>,
+    /// Consumer payment backing the mint
+    pub utxo_proof: [u8; 32],
+    /// Non-membership path for the proof's nullifier, required while the
+    /// nullifier accumulator is enabled
+    pub nullifier_siblings: Option<Vec<[u8; 32]>>,
+}
+
+/// Part of a prosumer share released linearly over time
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingGrant {
+    /// Tokens granted
+    pub total: u64,
+    /// Tokens already claimed
+    pub claimed: u64,
+    /// Block timestamp (seconds) vesting starts
+    pub start: u64,
+    /// Seconds until fully vested
+    pub duration: u64,
+}
+
+impl VestingGrant {
+    /// Tokens vested by `now`
+    pub fn vested_at(&self, now: u64) -> u64 {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= self.duration {
+            return self.total;
+        }
+        ((self.total as u128) * (elapsed as u128) / (self.duration as u128)) as u64
+    }
+}
+
+/// Decaying counters of mint proof verification outcomes
+#[derive(Debug, Clone, Default, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerificationStats {
+    pub attempts: u64,
+    pub verified: u64,
+    /// (reason, count), at most `MAX_REJECTION_REASONS` entries
+    pub rejections: Vec<(String, u64)>,
+}
+
+impl VerificationStats {
+    /// Count one outcome, halving every counter each `VERIFICATION_STATS_WINDOW`
+    /// attempts so recent outcomes dominate
+    pub fn record(&mut self, outcome: &Result<(), String>) {
+        if self.attempts >= VERIFICATION_STATS_WINDOW {
+            self.attempts /= 2;
+            self.verified /= 2;
+            for (_, count) in self.rejections.iter_mut() {
+                *count /= 2;
+            }
+            self.rejections.retain(|(_, count)| *count > 0);
+        }
+        
+        self.attempts += 1;
+        match outcome {
+            Ok(()) => self.verified += 1,
+            Err(reason) => {
+                let reason = if self.rejections.iter().any(|(r, _)| r == reason)
+                    || self.rejections.len() < MAX_REJECTION_REASONS - 1
+                {
+                    reason.clone()
+                } else {
+                    "Other".to_string()
+                };
+                match self.rejections.iter_mut().find(|(r, _)| *r == reason) {
+                    Some((_, count)) => *count += 1,
+                    None => self.rejections.push((reason, 1)),
+                }
+            }
+        }
+    }
+}
+
+/// Entry in the tamper-evident admin audit log
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminAction {
+    /// Admin message that was called
+    pub action: String,
+    /// Caller that performed it
+    pub caller: String,
+    /// Block timestamp (seconds)
+    pub timestamp: u64,
+    /// SHA256 of the encoded call parameters
+    pub params_hash: [u8; 32],
+    /// Hash of the previous entry, chaining the log
+    pub prev_hash: [u8; 32],
+}
+
+/// Maximum entries returned by one page of the admin log
+pub const MAX_ADMIN_LOG_PAGE: u32 = 100;
+
+/// Retained mint log entries; older ones are pruned after an archival event
+pub const MAX_MINT_LOG_LEN: u64 = 100_000;
+
+/// Maximum records returned by one page of `mints_in_range`
+pub const MAX_MINT_LOG_PAGE: u32 = 100;
+
+/// Hourly mint buckets retained for `mint_rate` (30 days)
+pub const MAX_MINT_RATE_HOURS: u64 = 30 * 24;
+
+/// One mint, as kept in the queryable mint log
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintRecord {
+    /// Position in the log; doubles as the pagination cursor
+    pub seq: u64,
+    /// Block timestamp (seconds) of the mint
+    pub timestamp: u64,
+    pub device_id: [u8; 32],
+    pub wallet: String,
+    /// Normalized energy credited (Wh)
+    pub energy_wh: u64,
+    /// Tokens minted in total (prosumer + protocol)
+    pub tokens: u64,
+    pub proof_id: [u8; 32],
+}
+
+/// Relayer instruction carried by the `AssetsBridged` event
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BridgeInstruction {
+    /// Layout version (`BRIDGE_INSTRUCTION_VERSION`)
+    pub version: u16,
+    /// Sequential burn identifier
+    pub burn_id: u64,
+    /// zkBTC-E burned
+    pub amount: u64,
+    /// USD value of backing the burn is worth
+    pub usd_value: u64,
+    /// Destination-chain fee (USD) withheld from the release
+    pub bridge_fee_usd: u64,
+    /// USD value the relayer must deliver (`usd_value - bridge_fee_usd`)
+    pub net_usd_value: u64,
+    /// Destination chain
+    pub dest_chain: String,
+    /// Recipient address on the destination chain
+    pub dest_address: String,
+    /// Block timestamp (seconds) by which the relayer must deliver
+    pub deadline: u64,
+}
+
 /// The main PoE zkBTC-E Minter contract
 #[wasm::contract]
 pub mod poe_zkbtc_minter {
@@ -92,13 +508,249 @@ pub mod poe_zkbtc_minter {
         /// UTXO commitment history (for cross-chain)
         #[state]
         pub utxo_commitments: StorageVec<[u8; 32]>,
+        
+        /// Last accepted cumulative energy counter per device (replay high-water mark)
+        #[state]
+        pub device_last_cumulative: Map<[u8; 32], u128>,
+        
+        /// Devices temporarily quarantined from minting
+        #[state]
+        pub paused_devices: Map<[u8; 32], bool>,
+        
+        /// Proofs already consumed by a mint (permanent, never evicted)
+        #[state]
+        pub proof_nullifiers: Map<[u8; 32], bool>,
+        
+        /// Proofs that passed verification (bounded, evicted least-recently-used first)
+        #[state]
+        pub verified_proof_cache: Map<[u8; 32], bool>,
+        
+        /// Cache recency order, oldest first
+        #[state]
+        pub proof_cache_order: Vec<[u8; 32]>,
+        
+        /// Maximum number of entries in `verified_proof_cache`
+        #[state]
+        pub proof_cache_capacity: u32,
+        
+        /// Lifetime prosumer tokens minted per wallet
+        #[state]
+        pub prosumer_minted: Map<String, u64>,
+        
+        /// Reverse index: payment (UTXO proof) -> mints it backed
+        #[state]
+        pub payment_mints: Map<[u8; 32], Vec<[u8; 32]>>,
+        
+        /// Mints flagged because their backing payment was revoked
+        #[state]
+        pub flagged_mints: Map<[u8; 32], String>,
+        
+        /// Max mints a single oracle may facilitate per window (0 = unlimited)
+        #[state]
+        pub oracle_rate_limit: u32,
+        
+        /// Length of the oracle rate-limit window in seconds
+        #[state]
+        pub oracle_rate_window: u64,
+        
+        /// Per-oracle (window start, mints in window)
+        #[state]
+        pub oracle_mint_counts: Map<[u8; 32], (u64, u32)>,
+        
+        /// Aggregate public key of the oracle signing group (threshold signatures)
+        #[state]
+        pub oracle_group_key: Option<[u8; 32]>,
+        
+        /// Signers (M of N) the group key was generated for; bound into every group
+        /// signature and never below `oracle_threshold`
+        #[state]
+        pub oracle_group_threshold: u32,
+        
+        /// Grid region of each device, set at certification
+        #[state]
+        pub device_region: Map<[u8; 32], u16>,
+        
+        /// Bumped whenever a device's region changes, so proofs bound to the old
+        /// classification stop verifying
+        #[state]
+        pub device_classification_version: Map<[u8; 32], u32>,
+        
+        /// Regional Wh-per-token ratio (falls back to `WH_PER_TOKEN`)
+        #[state]
+        pub region_wh_per_token: Map<u16, u64>,
+        
+        /// Number of burns bridged so far (next `burn_id`)
+        #[state]
+        pub burn_count: u64,
+        
+        /// Append-only log of admin actions
+        #[state]
+        pub admin_action_log: StorageVec<AdminAction>,
+        
+        /// Hash of the latest admin log entry
+        #[state]
+        pub admin_log_head: [u8; 32],
+        
+        /// How many blocks back a proof's anchor block hash may be
+        #[state]
+        pub max_anchor_depth: u64,
+        
+        /// Allowed drift between a proof's current time and block time (ms)
+        #[state]
+        pub proof_time_tolerance_ms: u64,
+        
+        /// Packet age proofs must have been generated against (ms)
+        #[state]
+        pub max_proof_age_ms: u64,
+        
+        /// Periodic (timestamp, energy total) snapshots per device
+        #[state]
+        pub device_energy_snapshot: Map<[u8; 32], Vec<(u64, u128)>>,
+        
+        /// Minimum seconds between snapshots of the same device
+        #[state]
+        pub snapshot_interval: u64,
+        
+        /// Protocol fee recipients and their weights in bps (sum to 10000)
+        #[state]
+        pub treasury_splits: Vec<(String, u16)>,
+        
+        /// Root of the sparse Merkle tree of spent nullifiers
+        #[state]
+        pub nullifier_root: [u8; 32],
+        
+        /// When set, new nullifiers go into the tree instead of `proof_nullifiers`
+        #[state]
+        pub nullifier_accumulator_enabled: bool,
+        
+        /// Role per oracle (absent = `MintAuthorized`)
+        #[state]
+        pub oracle_roles: Map<[u8; 32], OracleRole>,
+        
+        /// Energy reported by attest-only oracles, per device (analytics only)
+        #[state]
+        pub device_attested_energy: Map<[u8; 32], u128>,
+        
+        /// Highest cumulative counter attested per device (replay protection)
+        #[state]
+        pub device_attested_cumulative: Map<[u8; 32], u128>,
+        
+        /// Destination-chain fee (USD) withheld from each bridged burn
+        #[state]
+        pub bridge_fee: Map<String, u64>,
+        
+        /// Distinct oracle signatures required per mint (1 = single oracle)
+        #[state]
+        pub oracle_threshold: u32,
+        
+        /// Break-glass oracles awaiting ratification -> expiry timestamp (seconds)
+        #[state]
+        pub emergency_oracles: Map<[u8; 32], u64>,
+        
+        /// How long an emergency oracle stays active without ratification (seconds)
+        #[state]
+        pub emergency_oracle_ttl: u64,
+        
+        /// USD backing released per zkBTC-E burned
+        #[state]
+        pub price_per_token_usd: u64,
+        
+        /// Per-device vesting: (bps of the prosumer share vested, period in seconds)
+        #[state]
+        pub device_vesting: Map<[u8; 32], (u16, u64)>,
+        
+        /// Outstanding vesting grants per prosumer wallet
+        #[state]
+        pub vesting_grants: Map<String, Vec<VestingGrant>>,
+        
+        /// Spendable zkBTC-E per wallet (excludes unvested tokens)
+        #[state]
+        pub balances: Map<String, u64>,
+        
+        /// GrailVault contract allowed to burn holder balances for redemptions
+        #[state]
+        pub vault: String,
+        
+        /// Unit each device's packets report energy in
+        #[state]
+        pub energy_unit: Map<[u8; 32], EnergyUnit>,
+        
+        /// Lifetime output ceiling per device (Wh), set at certification
+        #[state]
+        pub device_lifetime_max_wh: Map<[u8; 32], u128>,
+        
+        /// Firmware version each device was certified on
+        #[state]
+        pub device_firmware: Map<[u8; 32], u32>,
+        
+        /// Firmware versions barred from minting for known reporting bugs
+        #[state]
+        pub blocked_firmware: Map<u32, bool>,
+        
+        /// Emergency stop for minting and redemptions
+        #[state]
+        pub paused: bool,
+        
+        /// Admin proposed by `propose_admin`, awaiting `accept_admin`
+        #[state]
+        pub pending_admin: Option<String>,
+        
+        /// Serialized Groth16 `VerifyingKey<Bls12>` per circuit `vk_hash`
+        #[state]
+        pub verifying_keys: Map<[u8; 32], Vec<u8>>,
+        
+        /// Items already committed per in-progress batch (batch id = hash of its items)
+        #[state]
+        pub batch_progress: Map<[u8; 32], u32>,
+        
+        /// Network this deployment runs on
+        #[state]
+        pub network: Network,
+        
+        /// Proof verification mode; fixed at deployment
+        #[state]
+        pub verification_mode: VerificationMode,
+        
+        /// Mint proof verification outcomes
+        #[state]
+        pub verification_stats: VerificationStats,
+        
+        /// Recent mints by sequence number, in block-time order
+        #[state]
+        pub mint_log: Map<u64, MintRecord>,
+        
+        /// Oldest retained sequence number in `mint_log`
+        #[state]
+        pub mint_log_start: u64,
+        
+        /// Sequence number of the next mint
+        #[state]
+        pub mint_log_next: u64,
+        
+        /// Tokens minted per hour as (hour since epoch, tokens), oldest first;
+        /// hours without mints have no bucket
+        #[state]
+        pub hourly_mints: Vec<(u64, u64)>,
     }
     
     #[contract(impl)]
     impl PoEzkBTCMinter {
-        /// Initialize contract with admin and treasury
+        /// Initialize contract with admin and treasury. `TrustedSkip` verification is
+        /// refused on mainnet.
         #[constructor]
-        pub fn new(admin: String, treasury: String) -> Self {
+        pub fn new(
+            admin: String,
+            treasury: String,
+            network: Network,
+            verification_mode: VerificationMode,
+        ) -> Self {
+            assert!(
+                !(network == Network::Mainnet && verification_mode == VerificationMode::TrustedSkip),
+                "Proof verification cannot be skipped on mainnet",
+            );
+            
+            let treasury_splits = vec![(treasury.clone(), BPS_DENOMINATOR)];
+            
             Self {
                 admin,
                 certified_devices: Map::new(),
@@ -109,135 +761,1712 @@ pub mod poe_zkbtc_minter {
                 treasury,
                 oracle_whitelist: Map::new(),
                 utxo_commitments: StorageVec::new(),
+                device_last_cumulative: Map::new(),
+                paused_devices: Map::new(),
+                proof_nullifiers: Map::new(),
+                verified_proof_cache: Map::new(),
+                proof_cache_order: Vec::new(),
+                proof_cache_capacity: DEFAULT_PROOF_CACHE_CAPACITY,
+                prosumer_minted: Map::new(),
+                payment_mints: Map::new(),
+                flagged_mints: Map::new(),
+                oracle_rate_limit: 0,
+                oracle_rate_window: 3_600,
+                oracle_mint_counts: Map::new(),
+                oracle_group_key: None,
+                oracle_group_threshold: 0,
+                device_region: Map::new(),
+                device_classification_version: Map::new(),
+                region_wh_per_token: Map::new(),
+                burn_count: 0,
+                admin_action_log: StorageVec::new(),
+                admin_log_head: [0u8; 32],
+                max_anchor_depth: DEFAULT_MAX_ANCHOR_DEPTH,
+                proof_time_tolerance_ms: DEFAULT_PROOF_TIME_TOLERANCE_MS,
+                max_proof_age_ms: DEFAULT_MAX_PROOF_AGE_MS,
+                device_energy_snapshot: Map::new(),
+                snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+                treasury_splits,
+                nullifier_root: Self::empty_nullifier_root(),
+                nullifier_accumulator_enabled: false,
+                oracle_roles: Map::new(),
+                device_attested_energy: Map::new(),
+                device_attested_cumulative: Map::new(),
+                bridge_fee: Map::new(),
+                oracle_threshold: 1,
+                emergency_oracles: Map::new(),
+                emergency_oracle_ttl: DEFAULT_EMERGENCY_ORACLE_TTL,
+                price_per_token_usd: TOKEN_PRICE_USD,
+                device_vesting: Map::new(),
+                vesting_grants: Map::new(),
+                balances: Map::new(),
+                vault: String::new(),
+                energy_unit: Map::new(),
+                device_lifetime_max_wh: Map::new(),
+                device_firmware: Map::new(),
+                blocked_firmware: Map::new(),
+                paused: false,
+                pending_admin: None,
+                verifying_keys: Map::new(),
+                batch_progress: Map::new(),
+                network,
+                verification_mode,
+                verification_stats: VerificationStats::default(),
+                mint_log: Map::new(),
+                mint_log_start: 0,
+                mint_log_next: 0,
+                hourly_mints: Vec::new(),
             }
         }
         
-        /// Certify a new energy device (DAO only)
+        /// Apply to register a device; the caller becomes its prosumer wallet and
+        /// the device stays `Pending` until the DAO certifies it
         #[message]
-        pub fn certify_device(
+        pub fn register_device(
             &mut self,
             device_id: [u8; 32],
-            prosumer_wallet: String,
+            region: u16,
+            unit: EnergyUnit,
         ) -> Result<(), String> {
-            // Only admin (DAO) can certify devices
-            self.ensure_admin()?;
-            
-            // Check device not already certified
+            // Check device not already registered
             if self.certified_devices.contains_key(&device_id) {
-                return Err("Device already certified".into());
+                return Err("Device already registered".into());
             }
             
+            let prosumer_wallet = wasm::caller();
+            
             // Register device
-            self.certified_devices.insert(device_id, DeviceStatus::Certified);
-            self.device_to_wallet.insert(device_id, prosumer_wallet);
+            self.certified_devices.insert(device_id, DeviceStatus::Pending);
+            self.device_to_wallet.insert(device_id, prosumer_wallet.clone());
+            self.device_region.insert(device_id, region);
+            self.energy_unit.insert(device_id, unit);
             
+            wasm::emit_event("DeviceRegistered", &DeviceRegisteredEvent {
+                device_id,
+                wallet: prosumer_wallet,
+                region,
+                unit,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
             Ok(())
         }
         
-        /// Mint zkBTC-E tokens with PoE proof
+        /// Approve a pending device for minting, recording the most energy (Wh) its
+        /// counter can plausibly reach over its lifetime and its firmware (DAO only)
         #[message]
-        pub fn mint_with_poe(
+        pub fn certify_device(
             &mut self,
-            poe_packet: PoEPacket,
-            zk_proof: ZkMintProof,
-            utxo_proof: [u8; 32], // Merkle proof of consumer payment
-        ) -> Result<u64, String> {
-            // 1. Verify device is certified
-            let status = self.certified_devices.get(&poe_packet.device_id)
-                .ok_or("Device not certified")?;
+            device_id: [u8; 32],
+            lifetime_max_wh: u128,
+            firmware_version: u32,
+        ) -> Result<(), String> {
+            // Only admin (DAO) can certify devices
+            self.ensure_admin()?;
+            self.record_admin_action("certify_device", &(device_id, lifetime_max_wh, firmware_version));
             
-            if status != DeviceStatus::Certified {
-                return Err("Device not active".into());
+            if lifetime_max_wh == 0 {
+                return Err("Lifetime ceiling must be non-zero".into());
             }
             
-            // 2. Verify oracle is whitelisted
-            if !self.oracle_whitelist.get(&poe_packet.oracle_id).unwrap_or(false) {
-                return Err("Oracle not authorized".into());
+            if self.blocked_firmware.get(&firmware_version).unwrap_or(false) {
+                return Err("Device firmware blocked".into());
             }
             
-            // 3. Verify zk-SNARK proof
-            self.verify_zk_proof(&zk_proof, &poe_packet)?;
-            
-            // 4. Verify UTXO payment proof
-            self.verify_utxo_payment(utxo_proof)?;
+            self.transition_device(device_id, &[DeviceStatus::Pending], DeviceStatus::Certified)?;
+            self.device_lifetime_max_wh.insert(device_id, lifetime_max_wh);
+            self.device_firmware.insert(device_id, firmware_version);
+            Ok(())
+        }
+        
+        /// Re-certify a device after a firmware update (DAO only)
+        #[message]
+        pub fn recertify_firmware(&mut self, device_id: [u8; 32], firmware_version: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("recertify_firmware", &(device_id, firmware_version));
             
-            // 5. Calculate tokens to mint (1 MWh = 1 zkBTC-E)
-            let tokens_to_mint = poe_packet.energy_wh / 1_000_000; // Convert Wh to MWh
+            match self.certified_devices.get(&device_id) {
+                Some(DeviceStatus::Certified) | Some(DeviceStatus::Suspended) => {}
+                Some(_) => return Err("Device not certified".into()),
+                None => return Err("Device not registered".into()),
+            }
             
-            if tokens_to_mint == 0 {
-                return Err("Insufficient energy for minting".into());
+            if self.blocked_firmware.get(&firmware_version).unwrap_or(false) {
+                return Err("Device firmware blocked".into());
             }
             
-            // 6. Update device energy total
-            let current_total = self.device_energy_total
+            self.device_firmware.insert(device_id, firmware_version);
+            wasm::emit_event("DeviceFirmwareUpdated", &DeviceFirmwareUpdatedEvent {
+                device_id,
+                firmware_version,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Block or unblock minting from devices on a firmware version (DAO only)
+        #[message]
+        pub fn set_firmware_blocked(&mut self, firmware_version: u32, blocked: bool) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_firmware_blocked", &(firmware_version, blocked));
+            
+            if blocked {
+                self.blocked_firmware.insert(firmware_version, true);
+            } else {
+                self.blocked_firmware.remove(&firmware_version);
+            }
+            
+            wasm::emit_event("FirmwareBlockUpdated", &FirmwareBlockUpdatedEvent {
+                firmware_version,
+                blocked,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Mint zkBTC-E tokens with PoE proof. While the nullifier accumulator is
+        /// enabled use `mint_with_nullifier_proof` instead.
+        #[message]
+        pub fn mint_with_poe(
+            &mut self,
+            poe_packet: PoEPacket,
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32], // Merkle proof of consumer payment
+        ) -> Result<u64, String> {
+            self.mint_single_oracle(poe_packet, zk_proof, utxo_proof, None)
+        }
+        
+        /// Mint a batch of packets, at most `max_items` (capped at `MAX_BATCH_ITEMS`) per
+        /// call, resuming after the items processed by earlier calls for the same batch.
+        /// Each item succeeds or fails on its own; returns the results for the items
+        /// processed by this call. Resubmit the same batch until all items are covered.
+        /// Each item carries its nullifier's non-membership path when the accumulator
+        /// is enabled, taken against the root left by the items before it.
+        #[message]
+        pub fn mint_batch(
+            &mut self,
+            items: Vec<(PoEPacket, ZkMintProof, [u8; 32], Option<Vec<[u8; 32]>>)>,
+            max_items: u32,
+        ) -> Result<Vec<Result<u64, String>>, String> {
+            self.ensure_not_paused()?;
+            
+            if items.len() > MAX_BATCH_LEN {
+                return Err("Batch too large".into());
+            }
+            if max_items == 0 {
+                return Err("max_items must be non-zero".into());
+            }
+            
+            let batch_id = sha256(&items.encode());
+            let offset = self.batch_progress.get(&batch_id).unwrap_or(0) as usize;
+            let end = items.len().min(offset + max_items.min(MAX_BATCH_ITEMS) as usize);
+            
+            let mut results = Vec::with_capacity(end - offset);
+            let mut total_tokens: u64 = 0;
+            for (poe_packet, zk_proof, utxo_proof, siblings) in items[offset..end].iter().cloned() {
+                let result = self.mint_single_oracle(poe_packet, zk_proof, utxo_proof, siblings.as_deref());
+                if let Ok(tokens) = result {
+                    total_tokens += tokens;
+                }
+                results.push(result);
+            }
+            
+            if end == items.len() {
+                self.batch_progress.remove(&batch_id);
+            } else {
+                self.batch_progress.insert(batch_id, end as u32);
+            }
+            
+            // Attest-only items come back as Ok(0): recorded, but nothing minted
+            let attested = results.iter().filter(|r| matches!(r, Ok(0))).count() as u32;
+            let failed = results.iter().filter(|r| r.is_err()).count() as u32;
+            let succeeded = results.len() as u32 - attested - failed;
+            wasm::emit_event("BatchMinted", &BatchMintedEvent {
+                batch_id,
+                processed: end as u32,
+                total_items: items.len() as u32,
+                succeeded,
+                attested,
+                failed,
+                tokens_minted: total_tokens,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(results)
+        }
+        
+        /// Mint from a bundled attestation; validated exactly as `mint_with_poe_quorum`
+        #[message]
+        pub fn mint_from_attestation(&mut self, attestation: Attestation) -> Result<u64, String> {
+            let Attestation { packet, zk_proof, signatures, utxo_proof, nullifier_siblings } = attestation;
+            self.mint_with_poe_quorum(packet, signatures, zk_proof, utxo_proof, nullifier_siblings)
+        }
+        
+        /// Mint with signatures from at least `oracle_threshold` distinct whitelisted
+        /// oracles over the same packet; `nullifier_siblings` is the non-membership
+        /// path required while the nullifier accumulator is enabled
+        #[message]
+        pub fn mint_with_poe_quorum(
+            &mut self,
+            poe_packet: PoEPacket,
+            signatures: Vec<([u8; 32], [u8; 64])>,
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32],
+            nullifier_siblings: Option<Vec<[u8; 32]>>,
+        ) -> Result<u64, String> {
+            self.ensure_not_paused()?;
+            self.ensure_device_can_mint(&poe_packet.device_id)?;
+            
+            let message = packet_signing_bytes(&poe_packet);
+            let mut signers: Vec<[u8; 32]> = Vec::new();
+            for (oracle_id, signature) in &signatures {
+                if signers.contains(oracle_id) {
+                    return Err("Duplicate oracle signature".into());
+                }
+                
+                if !self.is_oracle_authorized(oracle_id) {
+                    return Err("Oracle not authorized".into());
+                }
+                
+                let public_key = PublicKey::from_bytes(oracle_id)
+                    .map_err(|_| "Invalid oracle public key")?;
+                let signature = Signature::from_bytes(signature)
+                    .map_err(|_| "Malformed oracle signature")?;
+                public_key
+                    .verify(&message, &signature)
+                    .map_err(|_| "Invalid oracle signature")?;
+                
+                signers.push(*oracle_id);
+            }
+            
+            // Attest-only oracles sign validly but don't count toward the quorum
+            let minting_signers = signers
+                .iter()
+                .filter(|oracle_id| self.oracle_role(oracle_id) == OracleRole::MintAuthorized)
+                .count();
+            if (minting_signers as u32) < self.oracle_threshold.max(1) {
+                return Err("Oracle quorum not met".into());
+            }
+            
+            // The mint counts against the first signer's rate window
+            let authority = signers[0];
+            self.execute_mint(poe_packet, zk_proof, utxo_proof, authority, nullifier_siblings.as_deref())
+        }
+        
+        /// Mint with a non-membership proof (sibling path, leaf to root) showing the
+        /// proof's nullifier is not yet in the spent-nullifier tree. Quorum mints
+        /// pass the path to `mint_with_poe_quorum` instead.
+        #[message]
+        pub fn mint_with_nullifier_proof(
+            &mut self,
+            poe_packet: PoEPacket,
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32],
+            nullifier_siblings: Vec<[u8; 32]>,
+        ) -> Result<u64, String> {
+            self.mint_single_oracle(poe_packet, zk_proof, utxo_proof, Some(&nullifier_siblings))
+        }
+        
+        /// Mint with a FROST-style threshold signature from the oracle group over
+        /// `group_signing_bytes`, proving M-of-N oracles signed without revealing
+        /// which ones; `nullifier_siblings` as for `mint_with_poe_quorum`
+        #[message]
+        pub fn mint_with_threshold_signature(
+            &mut self,
+            poe_packet: PoEPacket,
+            group_signature: [u8; 64],
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32],
+            nullifier_siblings: Option<Vec<[u8; 32]>>,
+        ) -> Result<u64, String> {
+            self.ensure_not_paused()?;
+            self.ensure_device_can_mint(&poe_packet.device_id)?;
+            
+            let group_key = self.oracle_group_key.ok_or("No oracle group key")?;
+            
+            // The group must need at least as many oracles as a quorum mint
+            if self.oracle_group_threshold < self.oracle_threshold {
+                return Err("Group threshold below oracle quorum".into());
+            }
+            
+            // A threshold signature aggregates to a plain Ed25519 signature
+            // under the group key; fewer than `threshold` shares can't produce one
+            let public_key = PublicKey::from_bytes(&group_key)
+                .map_err(|_| "Invalid oracle group key")?;
+            let signature = Signature::from_bytes(&group_signature)
+                .map_err(|_| "Malformed group signature")?;
+            public_key
+                .verify(&group_signing_bytes(&poe_packet, self.oracle_group_threshold), &signature)
+                .map_err(|_| "Invalid group signature")?;
+            
+            self.execute_mint(poe_packet, zk_proof, utxo_proof, group_key, nullifier_siblings.as_deref())
+        }
+        
+        /// Burn zkBTC-E for backing assets
+        #[message]
+        pub fn burn_for_assets(
+            &mut self,
+            amount: u64,
+            recipient_chain: String,
+            recipient_address: String,
+            burn_proof: ZkMintProof,
+        ) -> Result<(), String> {
+            self.ensure_not_paused()?;
+            
+            if amount == 0 {
+                return Err("Zero burn amount".into());
+            }
+            
+            // Verify burn proof
+            self.verify_burn_proof(&burn_proof, amount)?;
+            
+            self.debit(&wasm::caller(), amount)?;
+            
+            // Conservation: can never burn more than was ever minted
+            let total_burned = self.total_burned.checked_add(amount).ok_or("Arithmetic overflow")?;
+            if total_burned > self.total_minted {
+                return Err("Burn exceeds minted".into());
+            }
+            
+            // Calculate asset value at the current token price
+            let usd_value = amount
+                .checked_mul(self.price_per_token_usd)
+                .ok_or("Arithmetic overflow")?;
+            
+            // The destination-chain fee comes out of what the relayer delivers
+            let bridge_fee_usd = self.bridge_fee.get(&recipient_chain).unwrap_or(0);
+            let net_usd_value = usd_value
+                .checked_sub(bridge_fee_usd)
+                .ok_or("Burn does not cover bridge fee")?;
+            
+            // Update burned total
+            self.total_burned = total_burned;
+            
+            // Emit cross-chain bridge event
+            let instruction = BridgeInstruction {
+                version: BRIDGE_INSTRUCTION_VERSION,
+                burn_id: self.burn_count,
+                amount,
+                usd_value,
+                bridge_fee_usd,
+                net_usd_value,
+                dest_chain: recipient_chain,
+                dest_address: recipient_address,
+                deadline: wasm::block_timestamp()
+                    .checked_add(DEFAULT_BRIDGE_DEADLINE_SECS)
+                    .ok_or("Arithmetic overflow")?,
+            };
+            self.burn_count += 1;
+            
+            wasm::emit_event("AssetsBridged", &AssetsBridgedEvent {
+                instruction,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Add oracle to whitelist (DAO only). An oracle's id is its Ed25519 key,
+        /// so each key can be registered once.
+        #[message]
+        pub fn add_oracle(&mut self, oracle_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("add_oracle", &oracle_id);
+            
+            PublicKey::from_bytes(&oracle_id).map_err(|_| "Invalid oracle key")?;
+            
+            if self.is_oracle_authorized(&oracle_id) {
+                return Err("Oracle already registered".into());
+            }
+            
+            // Supersede a lapsed emergency authorization
+            self.emergency_oracles.remove(&oracle_id);
+            self.oracle_whitelist.insert(oracle_id, true);
+            Ok(())
+        }
+        
+        /// Break-glass: authorize an oracle immediately, expiring after
+        /// `emergency_oracle_ttl` unless ratified (DAO only)
+        #[message]
+        pub fn emergency_add_oracle(&mut self, oracle_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("emergency_add_oracle", &oracle_id);
+            
+            PublicKey::from_bytes(&oracle_id).map_err(|_| "Invalid oracle key")?;
+            
+            if self.is_oracle_authorized(&oracle_id) {
+                return Err("Oracle already authorized".into());
+            }
+            
+            let expires_at = wasm::block_timestamp()
+                .checked_add(self.emergency_oracle_ttl)
+                .ok_or("Arithmetic overflow")?;
+            self.emergency_oracles.insert(oracle_id, expires_at);
+            wasm::emit_event("EmergencyOracleAdded", &EmergencyOracleAddedEvent {
+                oracle_id,
+                added_by: wasm::caller(),
+                expires_at,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Make an unexpired emergency oracle permanent (DAO only)
+        #[message]
+        pub fn ratify_emergency_oracle(&mut self, oracle_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("ratify_emergency_oracle", &oracle_id);
+            
+            let expires_at = self.emergency_oracles
+                .remove(&oracle_id)
+                .ok_or("Not an emergency oracle")?;
+            if wasm::block_timestamp() >= expires_at {
+                return Err("Emergency oracle expired".into());
+            }
+            
+            self.oracle_whitelist.insert(oracle_id, true);
+            wasm::emit_event("EmergencyOracleRatified", &EmergencyOracleRatifiedEvent {
+                oracle_id,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set how long emergency oracles last without ratification (DAO only)
+        #[message]
+        pub fn set_emergency_oracle_ttl(&mut self, ttl: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_emergency_oracle_ttl", &ttl);
+            
+            if ttl == 0 {
+                return Err("TTL must be non-zero".into());
+            }
+            
+            self.emergency_oracle_ttl = ttl;
+            Ok(())
+        }
+        
+        /// Set whether an oracle may mint or only attest (DAO only)
+        #[message]
+        pub fn set_oracle_role(&mut self, oracle_id: [u8; 32], role: OracleRole) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_oracle_role", &(oracle_id, role));
+            
+            if !self.is_oracle_authorized(&oracle_id) {
+                return Err("Oracle not authorized".into());
+            }
+            
+            self.oracle_roles.insert(oracle_id, role);
+            wasm::emit_event("OracleRoleChanged", &OracleRoleChangedEvent {
+                oracle_id,
+                role,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Whitelisted oracles with their settings, for migrating to another deployment
+        #[view]
+        pub fn export_oracles(&self) -> Vec<([u8; 32], OracleInfo)> {
+            self.oracle_whitelist
+                .iter()
+                .filter(|(_, authorized)| *authorized)
+                .map(|(oracle_id, _)| (oracle_id, OracleInfo { role: self.oracle_role(&oracle_id) }))
+                .collect()
+        }
+        
+        /// Whitelist a batch of exported oracles, skipping ones already present (DAO only).
+        /// Returns how many were added.
+        #[message]
+        pub fn import_oracles(&mut self, oracles: Vec<([u8; 32], OracleInfo)>) -> Result<u32, String> {
+            self.ensure_admin()?;
+            self.record_admin_action("import_oracles", &oracles);
+            
+            // Validate the whole batch before touching the whitelist
+            for (oracle_id, _) in &oracles {
+                if PublicKey::from_bytes(oracle_id).is_err() {
+                    return Err("Invalid oracle key".into());
+                }
+            }
+            
+            let mut imported = 0;
+            for (oracle_id, info) in oracles {
+                if self.oracle_whitelist.get(&oracle_id).unwrap_or(false) {
+                    continue;
+                }
+                
+                self.oracle_whitelist.insert(oracle_id, true);
+                self.oracle_roles.insert(oracle_id, info.role);
+                imported += 1;
+            }
+            
+            wasm::emit_event("OraclesImported", &OraclesImportedEvent {
+                imported,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(imported)
+        }
+        
+        /// Start handing admin to `new_admin`; takes effect once they accept (DAO only)
+        #[message]
+        pub fn propose_admin(&mut self, new_admin: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("propose_admin", &new_admin);
+            
+            if new_admin.is_empty() {
+                return Err("Admin must not be empty".into());
+            }
+            
+            self.pending_admin = Some(new_admin.clone());
+            wasm::emit_event("AdminTransferStarted", &AdminTransferStartedEvent {
+                admin: self.admin.clone(),
+                pending_admin: new_admin,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Complete an admin handoff (proposed admin only)
+        #[message]
+        pub fn accept_admin(&mut self) -> Result<(), String> {
+            let caller = wasm::caller();
+            if self.pending_admin.as_ref() != Some(&caller) {
+                return Err("Caller is not pending admin".into());
+            }
+            
+            let previous = core::mem::replace(&mut self.admin, caller.clone());
+            self.pending_admin = None;
+            self.record_admin_action("accept_admin", &previous);
+            
+            wasm::emit_event("AdminTransferCompleted", &AdminTransferCompletedEvent {
+                previous_admin: previous,
+                admin: caller,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Register the Groth16 verifying key proofs with `vk_hash` are checked against (DAO only)
+        #[message]
+        pub fn register_verifying_key(&mut self, vk_hash: [u8; 32], vk_bytes: Vec<u8>) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("register_verifying_key", &(vk_hash, sha256(&vk_bytes)));
+            
+            VerifyingKey::<Bls12>::read(&vk_bytes[..]).map_err(|_| "Malformed verifying key")?;
+            
+            self.verifying_keys.insert(vk_hash, vk_bytes);
+            wasm::emit_event("VerifyingKeyRegistered", &VerifyingKeyRegisteredEvent {
+                vk_hash,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Halt minting and redemptions; admin messages stay available (DAO only)
+        #[message]
+        pub fn pause(&mut self) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("pause", &());
+            
+            self.paused = true;
+            wasm::emit_event("Paused", &PauseEvent {
+                caller: wasm::caller(),
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Resume minting and redemptions (DAO only)
+        #[message]
+        pub fn unpause(&mut self) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("unpause", &());
+            
+            self.paused = false;
+            wasm::emit_event("Unpaused", &PauseEvent {
+                caller: wasm::caller(),
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Quarantine a single device from minting (DAO only)
+        #[message]
+        pub fn pause_device(&mut self, device_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("pause_device", &device_id);
+            
+            if !self.certified_devices.contains_key(&device_id) {
+                return Err("Device not registered".into());
+            }
+            
+            self.paused_devices.insert(device_id, true);
+            wasm::emit_event("DevicePaused", &DevicePauseEvent {
+                device_id,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Lift a device quarantine (DAO only)
+        #[message]
+        pub fn unpause_device(&mut self, device_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("unpause_device", &device_id);
+            
+            if self.paused_devices.remove(&device_id).is_none() {
+                return Err("Device not paused".into());
+            }
+            
+            wasm::emit_event("DeviceUnpaused", &DevicePauseEvent {
+                device_id,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Suspend a certified device (DAO only)
+        #[message]
+        pub fn suspend_device(&mut self, device_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("suspend_device", &device_id);
+            self.transition_device(device_id, &[DeviceStatus::Certified], DeviceStatus::Suspended)
+        }
+        
+        /// Return a suspended device to certified (DAO only)
+        #[message]
+        pub fn reinstate_device(&mut self, device_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("reinstate_device", &device_id);
+            self.transition_device(device_id, &[DeviceStatus::Suspended], DeviceStatus::Certified)
+        }
+        
+        /// Permanently retire a device; this is terminal (DAO only)
+        #[message]
+        pub fn decommission_device(&mut self, device_id: [u8; 32]) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("decommission_device", &device_id);
+            self.transition_device(
+                device_id,
+                &[DeviceStatus::Pending, DeviceStatus::Certified, DeviceStatus::Suspended],
+                DeviceStatus::Decommissioned,
+            )
+        }
+        
+        /// Re-anchor a device's cumulative counter after a meter reset (DAO only)
+        #[message]
+        pub fn reset_cumulative(
+            &mut self,
+            device_id: [u8; 32],
+            new_anchor: u128,
+            evidence: [u8; 32], // Hash of the replacement/reset evidence
+        ) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("reset_cumulative", &(device_id, new_anchor, evidence));
+            
+            if !self.certified_devices.contains_key(&device_id) {
+                return Err("Device not registered".into());
+            }
+            
+            if evidence == [0u8; 32] {
+                return Err("Missing reset evidence".into());
+            }
+            
+            let previous = self.device_last_cumulative
+                .get(&device_id)
+                .unwrap_or(0);
+            self.device_last_cumulative.insert(device_id, new_anchor);
+            
+            wasm::emit_event("CumulativeReset", &CumulativeResetEvent {
+                device_id,
+                previous,
+                new_anchor,
+                evidence,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Lifetime prosumer earnings of a wallet valued at the current token price
+        #[view]
+        pub fn prosumer_earnings_usd(&self, wallet: String) -> u64 {
+            self.prosumer_minted
+                .get(&wallet)
+                .unwrap_or(0)
+                .saturating_mul(self.price_per_token_usd)
+        }
+        
+        /// Flag every mint backed by a revoked payment (DAO only)
+        #[message]
+        pub fn flag_payment_mints(&mut self, txid: [u8; 32], reason: String) -> Result<u32, String> {
+            self.ensure_admin()?;
+            self.record_admin_action("flag_payment_mints", &(txid, &reason));
+            
+            let mints = self.payment_mints.get(&txid).unwrap_or_default();
+            for mint_id in &mints {
+                self.flagged_mints.insert(*mint_id, reason.clone());
+                wasm::emit_event("MintFlagged", &MintFlaggedEvent {
+                    mint_id: *mint_id,
+                    txid,
+                    reason: reason.clone(),
+                    schema_version: EVENT_SCHEMA_VERSION,
+                });
+            }
+            
+            Ok(mints.len() as u32)
+        }
+        
+        /// Move a device to another grid region, invalidating proofs generated
+        /// against its previous classification (DAO only)
+        #[message]
+        pub fn reclassify_device(&mut self, device_id: [u8; 32], region: u16) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("reclassify_device", &(device_id, region));
+            
+            let previous = self.device_region.get(&device_id).ok_or("Device not registered")?;
+            if previous == region {
+                return Err("Device already in region".into());
+            }
+            
+            let version = self.device_classification_version
+                .get(&device_id)
+                .unwrap_or(0)
+                .checked_add(1)
+                .ok_or("Arithmetic overflow")?;
+            self.device_region.insert(device_id, region);
+            self.device_classification_version.insert(device_id, version);
+            
+            wasm::emit_event("DeviceReclassified", &DeviceReclassifiedEvent {
+                device_id,
+                previous_region: previous,
+                region,
+                version,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the Wh-per-token ratio for a grid region (DAO only)
+        #[message]
+        pub fn set_region_wh_per_token(&mut self, region: u16, wh_per_token: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_region_wh_per_token", &(region, wh_per_token));
+            
+            if wh_per_token == 0 {
+                return Err("Ratio must be non-zero".into());
+            }
+            
+            self.region_wh_per_token.insert(region, wh_per_token);
+            wasm::emit_event("RegionRatioUpdated", &RegionRatioUpdatedEvent {
+                region,
+                wh_per_token,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Cap mints per oracle per window (DAO only)
+        #[message]
+        pub fn set_oracle_rate_limit(&mut self, limit: u32, window_secs: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_oracle_rate_limit", &(limit, window_secs));
+            
+            if window_secs == 0 {
+                return Err("Rate window must be non-zero".into());
+            }
+            
+            self.oracle_rate_limit = limit;
+            self.oracle_rate_window = window_secs;
+            Ok(())
+        }
+        
+        /// Inconsistencies in a device's state, for operators to repair before
+        /// they surface as failed mints
+        #[view]
+        pub fn check_device_integrity(&self, device_id: [u8; 32]) -> Vec<IntegrityIssue> {
+            let mut issues = Vec::new();
+            let has_wallet = self.device_to_wallet.contains_key(&device_id);
+            
+            match self.certified_devices.get(&device_id) {
+                Some(status) => {
+                    if status == DeviceStatus::Certified && !has_wallet {
+                        issues.push(IntegrityIssue::CertifiedWithoutWallet);
+                    }
+                    if status == DeviceStatus::Certified
+                        && !self.device_lifetime_max_wh.contains_key(&device_id)
+                    {
+                        issues.push(IntegrityIssue::MissingLifetimeCeiling);
+                    }
+                    if !self.energy_unit.contains_key(&device_id) {
+                        issues.push(IntegrityIssue::MissingEnergyUnit);
+                    }
+                    if !self.device_region.contains_key(&device_id) {
+                        issues.push(IntegrityIssue::MissingRegion);
+                    }
+                }
+                None => {
+                    if has_wallet {
+                        issues.push(IntegrityIssue::WalletWithoutRegistration);
+                    }
+                    if self.paused_devices.contains_key(&device_id) {
+                        issues.push(IntegrityIssue::PausedWithoutRegistration);
+                    }
+                    if self.device_energy_total.contains_key(&device_id)
+                        || self.device_last_cumulative.contains_key(&device_id)
+                    {
+                        issues.push(IntegrityIssue::EnergyWithoutRegistration);
+                    }
+                }
+            }
+            
+            issues
+        }
+        
+        /// Mint proof verification outcomes. A rejected standalone mint reverts
+        /// along with its stats update, so standalone mints only count when they
+        /// pass; every `mint_batch` item is counted.
+        #[view]
+        pub fn verification_stats(&self) -> VerificationStats {
+            self.verification_stats.clone()
+        }
+        
+        /// Run a mint's proof checks against `packet` without minting: the zk proof,
+        /// its public inputs, anchor, time and classification, and whether its
+        /// nullifier is spent. Nothing is cached or consumed, and each error names
+        /// the failed stage with `mint_with_poe`'s message. Nullifiers in the
+        /// accumulator need siblings to check, so only map-recorded ones are detected.
+        #[view]
+        pub fn verify_proof_only(&self, packet: PoEPacket, proof: ZkMintProof) -> Result<(), MinterError> {
+            if self.proof_nullifiers.contains_key(&Self::proof_id(&proof)) {
+                return Err(MinterError::ProofAlreadyUsed);
+            }
+            
+            self.verify_zk_proof(&proof, &packet).map_err(MinterError::InvalidProof)?;
+            self.verify_proof_anchor(&proof).map_err(MinterError::StaleAnchor)?;
+            self.verify_proof_time(&proof).map_err(MinterError::ProofTime)?;
+            self.verify_proof_classification(&proof, &packet).map_err(MinterError::Classification)
+        }
+        
+        /// Device energy total as of the latest snapshot at or before `timestamp`
+        #[view]
+        pub fn energy_at(&self, device_id: [u8; 32], timestamp: u64) -> Option<u128> {
+            self.device_energy_snapshot
+                .get(&device_id)?
+                .iter()
+                .rev()
+                .find(|(ts, _)| *ts <= timestamp)
+                .map(|(_, total)| *total)
+        }
+        
+        /// Set the minimum spacing between energy snapshots (DAO only)
+        #[message]
+        pub fn set_snapshot_interval(&mut self, interval_secs: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_snapshot_interval", &interval_secs);
+            
+            self.snapshot_interval = interval_secs;
+            Ok(())
+        }
+        
+        /// Replace the protocol fee recipients (DAO only)
+        #[message]
+        pub fn set_treasury_splits(&mut self, splits: Vec<(String, u16)>) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_treasury_splits", &splits);
+            
+            if splits.is_empty() {
+                return Err("No treasury recipients".into());
+            }
+            
+            if splits.iter().any(|(recipient, bps)| recipient.is_empty() || *bps == 0) {
+                return Err("Invalid treasury recipient".into());
+            }
+            
+            let total: u32 = splits.iter().map(|(_, bps)| *bps as u32).sum();
+            if total != BPS_DENOMINATOR as u32 {
+                return Err("Treasury splits must sum to 10000 bps".into());
+            }
+            
+            self.treasury_splits = splits;
+            Ok(())
+        }
+        
+        /// Set how many blocks old a proof anchor may be (DAO only)
+        #[message]
+        pub fn set_max_anchor_depth(&mut self, depth: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_max_anchor_depth", &depth);
+            
+            self.max_anchor_depth = depth;
+            Ok(())
+        }
+        
+        /// Set how far a proof's current time may drift from block time, in ms (DAO only)
+        #[message]
+        pub fn set_proof_time_tolerance(&mut self, tolerance_ms: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_proof_time_tolerance", &tolerance_ms);
+            
+            self.proof_time_tolerance_ms = tolerance_ms;
+            Ok(())
+        }
+        
+        /// Set the packet age proofs must enforce, in ms (DAO only)
+        #[message]
+        pub fn set_max_proof_age(&mut self, max_age_ms: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_max_proof_age", &max_age_ms);
+            
+            if max_age_ms == 0 {
+                return Err("Max proof age must be non-zero".into());
+            }
+            
+            self.max_proof_age_ms = max_age_ms;
+            Ok(())
+        }
+        
+        /// Vest `bps` of a device's prosumer share over `period` seconds;
+        /// `bps` of 0 turns vesting off (DAO only)
+        #[message]
+        pub fn set_device_vesting(&mut self, device_id: [u8; 32], bps: u16, period: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_device_vesting", &(device_id, bps, period));
+            
+            if !self.certified_devices.contains_key(&device_id) {
+                return Err("Device not registered".into());
+            }
+            
+            if bps == 0 {
+                self.device_vesting.remove(&device_id);
+            } else {
+                if bps > BPS_DENOMINATOR {
+                    return Err("Vesting share exceeds 100%".into());
+                }
+                if period == 0 {
+                    return Err("Vesting period must be non-zero".into());
+                }
+                self.device_vesting.insert(device_id, (bps, period));
+            }
+            
+            wasm::emit_event("DeviceVestingUpdated", &DeviceVestingUpdatedEvent {
+                device_id,
+                bps,
+                period,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Release the caller's vested tokens; returns the amount claimed
+        #[message]
+        pub fn claim_vested(&mut self) -> Result<u64, String> {
+            let wallet = wasm::caller();
+            let now = wasm::block_timestamp();
+            
+            let mut grants = self.vesting_grants.get(&wallet).ok_or("No vesting grants")?;
+            
+            let mut claimed = 0u64;
+            for grant in grants.iter_mut() {
+                let releasable = grant.vested_at(now) - grant.claimed;
+                grant.claimed += releasable;
+                claimed += releasable;
+            }
+            
+            if claimed == 0 {
+                return Err("Nothing vested yet".into());
+            }
+            
+            self.credit(&wallet, claimed)?;
+            
+            grants.retain(|grant| grant.claimed < grant.total);
+            if grants.is_empty() {
+                self.vesting_grants.remove(&wallet);
+            } else {
+                self.vesting_grants.insert(wallet.clone(), grants);
+            }
+            
+            wasm::emit_event("VestedClaimed", &VestedClaimedEvent {
+                wallet,
+                amount: claimed,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(claimed)
+        }
+        
+        /// Spendable zkBTC-E held by a wallet
+        #[view]
+        pub fn balance_of(&self, wallet: String) -> u64 {
+            self.balances.get(&wallet).unwrap_or(0)
+        }
+        
+        /// Set the vault allowed to burn balances for redemptions (DAO only)
+        #[message]
+        pub fn set_vault(&mut self, vault: String) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_vault", &vault);
+            
+            if vault.is_empty() {
+                return Err("Vault must not be empty".into());
+            }
+            
+            self.vault = vault;
+            Ok(())
+        }
+        
+        /// Burn a holder's tokens for a vault redemption (vault only)
+        #[message]
+        pub fn vault_burn(&mut self, wallet: String, amount: u64) -> Result<(), String> {
+            self.ensure_vault()?;
+            self.ensure_not_paused()?;
+            
+            let total_burned = self.total_burned.checked_add(amount).ok_or("Arithmetic overflow")?;
+            if total_burned > self.total_minted {
+                return Err("Burn exceeds minted".into());
+            }
+            
+            self.debit(&wallet, amount)?;
+            self.total_burned = total_burned;
+            Ok(())
+        }
+        
+        /// Return tokens of an expired, refunded redemption (vault only)
+        #[message]
+        pub fn vault_refund(&mut self, wallet: String, amount: u64) -> Result<(), String> {
+            self.ensure_vault()?;
+            
+            self.total_burned = self.total_burned.checked_sub(amount).ok_or("Arithmetic overflow")?;
+            self.credit(&wallet, amount)
+        }
+        
+        /// Tokens a wallet could claim right now
+        #[view]
+        pub fn claimable_vested(&self, wallet: String) -> u64 {
+            let now = wasm::block_timestamp();
+            self.vesting_grants
+                .get(&wallet)
+                .unwrap_or_default()
+                .iter()
+                .map(|grant| grant.vested_at(now) - grant.claimed)
+                .sum()
+        }
+        
+        /// Set the USD backing value of one zkBTC-E (DAO only)
+        #[message]
+        pub fn set_price_per_token(&mut self, new_price: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_price_per_token", &new_price);
+            
+            if new_price == 0 {
+                return Err("Price must be non-zero".into());
+            }
+            
+            self.price_per_token_usd = new_price;
+            wasm::emit_event("PriceUpdated", &PriceUpdatedEvent {
+                price_per_token_usd: new_price,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the USD fee withheld from burns bridged to `chain` (DAO only)
+        #[message]
+        pub fn set_bridge_fee(&mut self, chain: String, fee_usd: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_bridge_fee", &(chain.clone(), fee_usd));
+            
+            self.bridge_fee.insert(chain.clone(), fee_usd);
+            wasm::emit_event("BridgeFeeUpdated", &BridgeFeeUpdatedEvent {
+                chain,
+                fee_usd,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Switch new nullifiers to the sparse Merkle accumulator (DAO only).
+        /// Nullifiers already in `proof_nullifiers` stay checked in both modes.
+        #[message]
+        pub fn set_nullifier_accumulator(&mut self, enabled: bool) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_nullifier_accumulator", &enabled);
+            
+            self.nullifier_accumulator_enabled = enabled;
+            Ok(())
+        }
+        
+        /// Average tokens minted per hour over the last `window_hours`, the current
+        /// partial hour included; the window is clamped to `[1, MAX_MINT_RATE_HOURS]`
+        #[view]
+        pub fn mint_rate(&self, window_hours: u64) -> u64 {
+            let window = window_hours.clamp(1, MAX_MINT_RATE_HOURS);
+            let hour = wasm::block_timestamp() / 3_600;
+            
+            let minted: u128 = self.hourly_mints
+                .iter()
+                .filter(|(bucket, _)| bucket.saturating_add(window) > hour)
+                .map(|(_, tokens)| *tokens as u128)
+                .sum();
+            (minted / window as u128) as u64
+        }
+        
+        /// Mints with block timestamps in `[start_ts, end_ts]`, oldest first. Pass the
+        /// returned cursor back to continue; `None` means the range is exhausted.
+        /// Only the last `MAX_MINT_LOG_LEN` mints are retained.
+        #[view]
+        pub fn mints_in_range(
+            &self,
+            start_ts: u64,
+            end_ts: u64,
+            cursor: Option<u64>,
+            limit: u32,
+        ) -> (Vec<MintRecord>, Option<u64>) {
+            let limit = limit.min(MAX_MINT_LOG_PAGE) as usize;
+            
+            // Records are appended in block-time order, so binary search for the
+            // first one at or after `start_ts`
+            let (mut lo, mut hi) = (self.mint_log_start, self.mint_log_next);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                match self.mint_log.get(&mid) {
+                    Some(record) if record.timestamp < start_ts => lo = mid + 1,
+                    _ => hi = mid,
+                }
+            }
+            
+            let mut seq = cursor.map_or(lo, |cursor| cursor.max(lo));
+            let mut records = Vec::new();
+            while seq < self.mint_log_next {
+                let Some(record) = self.mint_log.get(&seq) else { break };
+                if record.timestamp > end_ts {
+                    return (records, None);
+                }
+                if records.len() == limit {
+                    return (records, Some(seq));
+                }
+                records.push(record);
+                seq += 1;
+            }
+            
+            (records, None)
+        }
+        
+        /// Page through the admin audit log, oldest first
+        #[view]
+        pub fn admin_actions(&self, offset: u64, limit: u32) -> Vec<AdminAction> {
+            let limit = limit.min(MAX_ADMIN_LOG_PAGE) as u64;
+            let end = (offset + limit).min(self.admin_action_log.len() as u64);
+            
+            (offset..end)
+                .filter_map(|i| self.admin_action_log.get(i as usize))
+                .collect()
+        }
+        
+        /// Resize the proof verification cache (DAO only)
+        #[message]
+        pub fn set_proof_cache_capacity(&mut self, capacity: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_proof_cache_capacity", &capacity);
+            self.proof_cache_capacity = capacity;
+            
+            // Shrinking evicts immediately; nullifiers are unaffected
+            while self.proof_cache_order.len() > capacity as usize {
+                let oldest = self.proof_cache_order.remove(0);
+                self.verified_proof_cache.remove(&oldest);
+            }
+            
+            Ok(())
+        }
+        
+        /// Set how many distinct oracle signatures a mint needs (DAO only)
+        #[message]
+        pub fn set_oracle_threshold(&mut self, threshold: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_oracle_threshold", &threshold);
+            
+            if threshold == 0 {
+                return Err("Threshold must be non-zero".into());
+            }
+            
+            let whitelisted = self.oracle_whitelist
+                .iter()
+                .filter(|(_, authorized)| *authorized)
+                .count();
+            if threshold as usize > whitelisted {
+                return Err("Threshold exceeds whitelisted oracles".into());
+            }
+            
+            self.oracle_threshold = threshold;
+            wasm::emit_event("OracleThresholdUpdated", &OracleThresholdUpdatedEvent {
+                threshold,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Set the oracle group key and threshold for threshold-signed mints (DAO only)
+        #[message]
+        pub fn set_oracle_group_key(&mut self, group_key: [u8; 32], threshold: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.record_admin_action("set_oracle_group_key", &(group_key, threshold));
+            
+            if threshold == 0 {
+                return Err("Threshold must be non-zero".into());
+            }
+            if threshold < self.oracle_threshold {
+                return Err("Group threshold below oracle quorum".into());
+            }
+            PublicKey::from_bytes(&group_key).map_err(|_| "Invalid oracle group key")?;
+            
+            self.oracle_group_key = Some(group_key);
+            self.oracle_group_threshold = threshold;
+            wasm::emit_event("OracleGroupKeyUpdated", &OracleGroupKeyUpdatedEvent {
+                group_key,
+                threshold,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        // Internal helper functions
+        /// Single-oracle mint shared by `mint_with_poe`, `mint_batch` and
+        /// `mint_with_nullifier_proof`
+        fn mint_single_oracle(
+            &mut self,
+            poe_packet: PoEPacket,
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32],
+            nullifier_siblings: Option<&[[u8; 32]]>,
+        ) -> Result<u64, String> {
+            self.ensure_not_paused()?;
+            
+            // 1. Verify device is certified
+            self.ensure_device_can_mint(&poe_packet.device_id)?;
+            
+            // 2. Verify oracle is whitelisted
+            if !self.is_oracle_authorized(&poe_packet.oracle_id) {
+                return Err("Oracle not authorized".into());
+            }
+            
+            // 3. Verify the oracle actually signed this packet
+            self.verify_oracle_signature(&poe_packet)?;
+            
+            // Attest-only oracles feed analytics but never mint
+            if self.oracle_role(&poe_packet.oracle_id) == OracleRole::AttestOnly {
+                self.record_attestation(poe_packet, zk_proof, nullifier_siblings)?;
+                return Ok(0);
+            }
+            
+            if self.oracle_threshold > 1 {
+                return Err("Oracle quorum required".into());
+            }
+            
+            let oracle_id = poe_packet.oracle_id;
+            self.execute_mint(poe_packet, zk_proof, utxo_proof, oracle_id, nullifier_siblings)
+        }
+        
+        /// Shared mint path once the device and signing authority are checked;
+        /// `authority` is the oracle (or oracle group) the mint counts against and
+        /// `nullifier_siblings` the non-membership path when the accumulator is used
+        fn execute_mint(
+            &mut self,
+            poe_packet: PoEPacket,
+            zk_proof: ZkMintProof,
+            utxo_proof: [u8; 32],
+            authority: [u8; 32],
+            nullifier_siblings: Option<&[[u8; 32]]>,
+        ) -> Result<u64, String> {
+            let oracle_window = self.oracle_window_count(&authority);
+            if self.oracle_rate_limit > 0 && oracle_window.1 >= self.oracle_rate_limit {
+                return Err("Oracle rate limit".into());
+            }
+            
+            // 4. Verify zk-SNARK proof (each proof can only mint once)
+            let proof_id = Self::proof_id(&zk_proof);
+            let verification = self.verify_mint_proof(proof_id, &zk_proof, &poe_packet, nullifier_siblings);
+            self.verification_stats.record(&verification.clone().map(|_| ()));
+            
+            // A proof that verified stays cached even if a later check rejects the
+            // mint, so the retry skips the pairing check
+            self.remember_verified_proof(verification?);
+            
+            // 5. Verify UTXO payment proof
+            self.verify_utxo_payment(utxo_proof)?;
+            
+            // Replay protection: the meter's cumulative counter must advance
+            let previous_cumulative = self.device_last_cumulative
                 .get(&poe_packet.device_id)
                 .unwrap_or(0);
-            self.device_energy_total.insert(
-                poe_packet.device_id,
-                current_total + poe_packet.energy_wh as u128
-            );
+            if poe_packet.cumulative_energy <= previous_cumulative {
+                return Err("Stale cumulative energy".into());
+            }
             
-            // 7. Apply distribution split (85/15)
-            let prosumer_tokens = (tokens_to_mint * 85) / 100;
-            let protocol_tokens = tokens_to_mint - prosumer_tokens;
+            let unit = self.energy_unit
+                .get(&poe_packet.device_id)
+                .ok_or("Unknown energy unit")?;
+            
+            // A counter past the device's rated lifetime output means a corrupted meter
+            if let Some(max_wh) = self.device_lifetime_max_wh.get(&poe_packet.device_id) {
+                let cumulative_wh = unit
+                    .to_wh(poe_packet.cumulative_energy)
+                    .ok_or("Arithmetic overflow")?;
+                if cumulative_wh > max_wh {
+                    return Err("Cumulative energy exceeds device lifetime".into());
+                }
+            }
+            
+            // Credit the counter delta, capped at the energy the proof attests to;
+            // both are in the device's unit, so normalize to Wh afterwards
+            let cumulative_delta = poe_packet.cumulative_energy - previous_cumulative;
+            let minted = cumulative_delta.min(poe_packet.energy_wh as u128);
+            let minted_wh: u64 = unit
+                .to_wh(minted)
+                .and_then(|wh| wh.try_into().ok())
+                .ok_or("Arithmetic overflow")?;
+            
+            // 6. Calculate tokens to mint (1 MWh = 1 zkBTC-E unless the region differs)
+            let tokens_to_mint = minted_wh / self.wh_per_token(&poe_packet.device_id);
+            
+            if tokens_to_mint == 0 {
+                return Err("Insufficient energy for minting".into());
+            }
             
-            // 8. Get prosumer wallet
+            // 7. Get prosumer wallet
             let prosumer_wallet = self.device_to_wallet
                 .get(&poe_packet.device_id)
                 .ok_or("No wallet mapped to device")?;
             
-            // 9. Mint tokens (simplified - in reality would call Charms minting)
-            self.total_minted += tokens_to_mint;
+            // Run every fallible check before touching state, so a rejected mint
+            // leaves nothing behind. Balances never exceed supply, so the credits
+            // below cannot overflow once the supply bump succeeds.
+            let current_total = self.device_energy_total
+                .get(&poe_packet.device_id)
+                .unwrap_or(0);
+            let new_total = current_total
+                .checked_add(minted_wh as u128)
+                .ok_or("Arithmetic overflow")?;
+            let new_supply = self.total_minted
+                .checked_add(tokens_to_mint)
+                .ok_or("Arithmetic overflow")?;
+            
+            // Mints draw on the vault's growth-capped backing, and its circulating
+            // supply is kept in step so the tokens can be redeemed
+            if !self.vault.is_empty() {
+                let capacity: u64 = wasm::call_view(&self.vault, "mint_capacity", &())?;
+                if tokens_to_mint > capacity {
+                    return Err("Mint exceeds eligible backing".into());
+                }
+                wasm::call::<_, ()>(&self.vault, "record_mint", &tokens_to_mint)?;
+            }
+            
+            // Only a mint that passed every check commits its payment
+            self.utxo_commitments.push(utxo_proof);
+            
+            // 8. Update device energy total
+            self.device_energy_total.insert(poe_packet.device_id, new_total);
+            self.record_energy_snapshot(poe_packet.device_id, new_total);
+            
+            // 9. Apply distribution split (85/15)
+            let prosumer_tokens = ((tokens_to_mint as u128) * 85 / 100) as u64;
+            let protocol_tokens = tokens_to_mint - prosumer_tokens;
+            
+            // 10. Mint tokens (simplified - in reality would call Charms minting)
+            self.total_minted = new_supply;
+            
+            // Consume the proof
+            self.spend_nullifier(proof_id, nullifier_siblings);
+            
+            // Advance the replay high-water mark
+            self.device_last_cumulative.insert(poe_packet.device_id, poe_packet.cumulative_energy);
             
-            // 10. Emit events for frontend
-            wasm::emit_event("PoEMinted", &(
-                poe_packet.device_id,
-                tokens_to_mint,
-                prosumer_tokens,
-                protocol_tokens,
-            ));
+            // Count the mint against the oracle's window
+            self.oracle_mint_counts.insert(
+                authority,
+                (oracle_window.0, oracle_window.1.saturating_add(1)),
+            );
+            
+            // Index the mint under the payment that backed it
+            let mut backed = self.payment_mints.get(&utxo_proof).unwrap_or_default();
+            backed.push(proof_id);
+            self.payment_mints.insert(utxo_proof, backed);
+            
+            // Record prosumer earnings
+            let earned = self.prosumer_minted.get(&prosumer_wallet).unwrap_or(0);
+            let earned = earned.checked_add(prosumer_tokens).ok_or("Arithmetic overflow")?;
+            self.prosumer_minted.insert(prosumer_wallet.clone(), earned);
+            
+            // Hold back the device's vested portion of the prosumer share
+            let mut vested = 0;
+            if let Some((bps, period)) = self.device_vesting.get(&poe_packet.device_id) {
+                vested = ((prosumer_tokens as u128) * (bps as u128) / BPS_DENOMINATOR as u128) as u64;
+                if vested > 0 {
+                    let mut grants = self.vesting_grants.get(&prosumer_wallet).unwrap_or_default();
+                    grants.push(VestingGrant {
+                        total: vested,
+                        claimed: 0,
+                        start: wasm::block_timestamp(),
+                        duration: period,
+                    });
+                    self.vesting_grants.insert(prosumer_wallet.clone(), grants);
+                }
+            }
+            self.credit(&prosumer_wallet, prosumer_tokens - vested)?;
+            
+            // Distribute protocol share across treasury recipients
+            let protocol_payouts = self.split_protocol_tokens(protocol_tokens);
+            for (recipient, share) in &protocol_payouts {
+                self.credit(recipient, *share)?;
+            }
+            wasm::emit_event("ProtocolFeesDistributed", &ProtocolFeesDistributedEvent {
+                payouts: protocol_payouts,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            // 11. Emit events for frontend
+            wasm::emit_event("PoEMinted", &PoEMintedEvent {
+                device_id: poe_packet.device_id,
+                total: tokens_to_mint,
+                prosumer: prosumer_tokens,
+                protocol: protocol_tokens,
+                energy_source: poe_packet.energy_source,
+                grid_id: poe_packet.grid_id,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            self.append_mint_log(MintRecord {
+                seq: self.mint_log_next,
+                timestamp: wasm::block_timestamp(),
+                device_id: poe_packet.device_id,
+                wallet: prosumer_wallet,
+                energy_wh: minted_wh,
+                tokens: tokens_to_mint,
+                proof_id,
+            });
+            self.record_hourly_mint(tokens_to_mint);
             
             Ok(tokens_to_mint)
         }
         
-        /// Burn zkBTC-E for backing assets
-        #[message]
-        pub fn burn_for_assets(
+        /// Split protocol tokens by treasury weights; rounding dust goes to the first recipient
+        fn split_protocol_tokens(&self, amount: u64) -> Vec<(String, u64)> {
+            let mut payouts: Vec<(String, u64)> = self.treasury_splits
+                .iter()
+                .map(|(recipient, bps)| {
+                    let share = (amount as u128) * (*bps as u128) / BPS_DENOMINATOR as u128;
+                    (recipient.clone(), share as u64)
+                })
+                .collect();
+            
+            let distributed: u64 = payouts.iter().map(|(_, share)| *share).sum();
+            if let Some((_, first)) = payouts.first_mut() {
+                *first += amount - distributed;
+            }
+            
+            payouts
+        }
+        
+        /// Append to the mint log, archiving the oldest record once it is full
+        fn append_mint_log(&mut self, record: MintRecord) {
+            self.mint_log.insert(record.seq, record);
+            self.mint_log_next += 1;
+            
+            if self.mint_log_next - self.mint_log_start > MAX_MINT_LOG_LEN {
+                if let Some(oldest) = self.mint_log.remove(&self.mint_log_start) {
+                    wasm::emit_event("MintArchived", &MintArchivedEvent {
+                        record: oldest,
+                        schema_version: EVENT_SCHEMA_VERSION,
+                    });
+                }
+                self.mint_log_start += 1;
+            }
+        }
+        
+        /// Add minted tokens to the current hour's bucket, dropping buckets that
+        /// have aged out of `MAX_MINT_RATE_HOURS`
+        fn record_hourly_mint(&mut self, tokens: u64) {
+            let hour = wasm::block_timestamp() / 3_600;
+            
+            match self.hourly_mints.last_mut() {
+                Some((last, total)) if *last == hour => *total = total.saturating_add(tokens),
+                _ => self.hourly_mints.push((hour, tokens)),
+            }
+            
+            let retained = self.hourly_mints
+                .iter()
+                .position(|(bucket, _)| bucket + MAX_MINT_RATE_HOURS > hour)
+                .unwrap_or(self.hourly_mints.len());
+            self.hourly_mints.drain(..retained);
+        }
+        
+        /// Snapshot a device's energy total if the interval has elapsed
+        fn record_energy_snapshot(&mut self, device_id: [u8; 32], total: u128) {
+            let now = wasm::block_timestamp();
+            let mut snapshots = self.device_energy_snapshot.get(&device_id).unwrap_or_default();
+            
+            if let Some((last, _)) = snapshots.last() {
+                if now < last.saturating_add(self.snapshot_interval) {
+                    return;
+                }
+            }
+            
+            snapshots.push((now, total));
+            if snapshots.len() > MAX_SNAPSHOTS_PER_DEVICE {
+                snapshots.remove(0);
+            }
+            self.device_energy_snapshot.insert(device_id, snapshots);
+        }
+        
+        /// Move a device to `to` if its current status is one of `from`
+        fn transition_device(
             &mut self,
-            amount: u64,
-            recipient_chain: String,
-            recipient_address: String,
-            burn_proof: ZkMintProof,
+            device_id: [u8; 32],
+            from: &[DeviceStatus],
+            to: DeviceStatus,
         ) -> Result<(), String> {
-            // Verify burn proof
-            self.verify_burn_proof(&burn_proof, amount)?;
+            let old = self.certified_devices
+                .get(&device_id)
+                .ok_or("Device not registered")?;
             
-            // Update burned total
-            self.total_burned += amount;
+            if !from.contains(&old) {
+                return Err("Illegal device status transition".into());
+            }
             
-            // Calculate asset value (1 zkBTC-E = $70 in backing)
-            let usd_value = amount * 70;
+            self.certified_devices.insert(device_id, to.clone());
+            wasm::emit_event("DeviceStatusChanged", &DeviceStatusChangedEvent {
+                device_id,
+                from: old,
+                to,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            Ok(())
+        }
+        
+        /// Energy needed per token for a device, by its region
+        fn wh_per_token(&self, device_id: &[u8; 32]) -> u64 {
+            self.device_region
+                .get(device_id)
+                .and_then(|region| self.region_wh_per_token.get(&region))
+                .unwrap_or(WH_PER_TOKEN)
+        }
+        
+        fn ensure_device_can_mint(&self, device_id: &[u8; 32]) -> Result<(), String> {
+            let status = self.certified_devices.get(device_id)
+                .ok_or("Device not certified")?;
             
-            // Emit cross-chain bridge event
-            wasm::emit_event("AssetsBridged", &(
-                amount,
-                usd_value,
-                recipient_chain,
-                recipient_address,
-            ));
+            if status != DeviceStatus::Certified {
+                return Err("Device not active".into());
+            }
+            
+            if self.paused_devices.get(device_id).unwrap_or(false) {
+                return Err("Device minting paused".into());
+            }
+            
+            let blocked = self.device_firmware
+                .get(device_id)
+                .map_or(false, |version| self.blocked_firmware.get(&version).unwrap_or(false));
+            if blocked {
+                return Err("Device firmware blocked".into());
+            }
             
             Ok(())
         }
         
-        /// Add oracle to whitelist (DAO only)
-        #[message]
-        pub fn add_oracle(&mut self, oracle_id: [u8; 32]) -> Result<(), String> {
-            self.ensure_admin()?;
-            self.oracle_whitelist.insert(oracle_id, true);
+        /// Whitelisted, or an emergency oracle whose timelock hasn't run out
+        fn is_oracle_authorized(&self, oracle_id: &[u8; 32]) -> bool {
+            if self.oracle_whitelist.get(oracle_id).unwrap_or(false) {
+                return true;
+            }
+            
+            self.emergency_oracles
+                .get(oracle_id)
+                .map_or(false, |expires_at| wasm::block_timestamp() < expires_at)
+        }
+        
+        fn oracle_role(&self, oracle_id: &[u8; 32]) -> OracleRole {
+            self.oracle_roles.get(oracle_id).unwrap_or(OracleRole::MintAuthorized)
+        }
+        
+        /// Record an attest-only packet in analytics without minting. It passes the
+        /// same proof, replay and counter checks as a mint and spends the proof, so
+        /// a resubmitted packet can't inflate the attested total.
+        fn record_attestation(
+            &mut self,
+            packet: PoEPacket,
+            zk_proof: ZkMintProof,
+            nullifier_siblings: Option<&[[u8; 32]]>,
+        ) -> Result<(), String> {
+            let proof_id = Self::proof_id(&zk_proof);
+            self.verify_mint_proof(proof_id, &zk_proof, &packet, nullifier_siblings)?;
+            
+            // Past both what was minted and what was already attested
+            let previous_cumulative = self.device_last_cumulative
+                .get(&packet.device_id)
+                .unwrap_or(0)
+                .max(self.device_attested_cumulative.get(&packet.device_id).unwrap_or(0));
+            if packet.cumulative_energy <= previous_cumulative {
+                return Err("Stale cumulative energy".into());
+            }
+            
+            self.spend_nullifier(proof_id, nullifier_siblings);
+            self.device_attested_cumulative.insert(packet.device_id, packet.cumulative_energy);
+            
+            let attested = self.device_attested_energy.get(&packet.device_id).unwrap_or(0);
+            self.device_attested_energy.insert(
+                packet.device_id,
+                attested.saturating_add(packet.energy_wh as u128),
+            );
+            
+            wasm::emit_event("EnergyAttested", &EnergyAttestedEvent {
+                device_id: packet.device_id,
+                oracle_id: packet.oracle_id,
+                energy_wh: packet.energy_wh,
+                cumulative_energy: packet.cumulative_energy,
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            
+            Ok(())
+        }
+        
+        /// Check the packet's Ed25519 signature against its `oracle_id` public key
+        fn verify_oracle_signature(&self, packet: &PoEPacket) -> Result<(), String> {
+            let public_key = PublicKey::from_bytes(&packet.oracle_id)
+                .map_err(|_| "Invalid oracle public key")?;
+            let signature = Signature::from_bytes(&packet.signature)
+                .map_err(|_| "Malformed oracle signature")?;
+            
+            public_key
+                .verify(&packet_signing_bytes(packet), &signature)
+                .map_err(|_| "Invalid oracle signature")?;
+            
+            Ok(())
+        }
+        
+        fn ensure_not_paused(&self) -> Result<(), String> {
+            if self.paused {
+                return Err("Contract is paused".into());
+            }
+            Ok(())
+        }
+        
+        fn ensure_vault(&self) -> Result<(), String> {
+            if self.vault.is_empty() || wasm::caller() != self.vault {
+                return Err("Caller is not vault".into());
+            }
+            Ok(())
+        }
+        
+        fn credit(&mut self, wallet: &str, amount: u64) -> Result<(), String> {
+            let balance = self.balances.get(wallet).unwrap_or(0);
+            let balance = balance.checked_add(amount).ok_or("Arithmetic overflow")?;
+            self.balances.insert(wallet.to_string(), balance);
+            Ok(())
+        }
+        
+        fn debit(&mut self, wallet: &str, amount: u64) -> Result<(), String> {
+            let balance = self.balances.get(wallet).unwrap_or(0);
+            let balance = balance.checked_sub(amount).ok_or("Insufficient balance")?;
+            self.balances.insert(wallet.to_string(), balance);
             Ok(())
         }
         
-        // Internal helper functions
         fn ensure_admin(&self) -> Result<(), String> {
             let caller = wasm::caller();
             if caller != self.admin {
@@ -246,6 +2475,24 @@ pub mod poe_zkbtc_minter {
             Ok(())
         }
         
+        /// Append an admin action to the hash-chained audit log
+        fn record_admin_action<P: Encode>(&mut self, action: &str, params: &P) {
+            let entry = AdminAction {
+                action: action.into(),
+                caller: wasm::caller(),
+                timestamp: wasm::block_timestamp(),
+                params_hash: sha256(&params.encode()),
+                prev_hash: self.admin_log_head,
+            };
+            
+            self.admin_log_head = sha256(&entry.encode());
+            wasm::emit_event("AdminAction", &AdminActionEvent {
+                entry: entry.clone(),
+                schema_version: EVENT_SCHEMA_VERSION,
+            });
+            self.admin_action_log.push(entry);
+        }
+        
         fn verify_zk_proof(&self, proof: &ZkMintProof, packet: &PoEPacket) -> Result<(), String> {
             // In production, this would verify the zk-SNARK proof
             // For now, we simulate verification
@@ -255,30 +2502,246 @@ pub mod poe_zkbtc_minter {
                 return Err("Invalid proof length".into());
             }
             
-            // Verify VK hash matches known circuit
-            let expected_vk_hash = hex::decode("1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef")
-                .map_err(|_| "Invalid hex")?;
-            
-            if proof.vk_hash != expected_vk_hash.as_slice().try_into().unwrap() {
-                return Err("Invalid verification key".into());
+            if proof.public_inputs.len() != POE_PUBLIC_INPUT_COUNT {
+                return Err("Wrong number of proof public inputs".into());
             }
             
-            // Verify public inputs match packet data
-            let energy_hash = sha256(&packet.energy_wh.to_be_bytes());
-            if proof.public_inputs[0] != energy_hash {
+            // The verifying key is looked up by `vk_hash`, so only registered circuits pass
+            self.verify_groth16(proof)?;
+            
+            // Verify public inputs match packet data (the circuit takes energy as a
+            // plain field element, not a hash)
+            if proof.public_inputs[PUBLIC_INPUT_ENERGY] != public_input_from_u64(packet.energy_wh) {
                 return Err("Proof doesn't match energy data".into());
             }
             
+            // Bind the proof to the claimed device so it can't be retargeted
+            if proof.public_inputs[PUBLIC_INPUT_DEVICE] != public_input_digest(&packet.device_id) {
+                return Err("Proof doesn't match device".into());
+            }
+            
+            // The circuit's freshness window is measured from this reading time
+            if proof.public_inputs[PUBLIC_INPUT_TIMESTAMP] != public_input_from_u64(packet.timestamp) {
+                return Err("Proof doesn't match packet timestamp".into());
+            }
+            
+            Ok(())
+        }
+        
+        /// Require the proof to commit to one of the last `max_anchor_depth` block hashes.
+        /// Kept outside the proof cache since freshness changes as blocks arrive.
+        fn verify_proof_anchor(&self, proof: &ZkMintProof) -> Result<(), String> {
+            let anchor = proof.public_inputs
+                .get(PUBLIC_INPUT_ANCHOR)
+                .ok_or("Missing proof anchor")?;
+            
+            let height = wasm::block_height();
+            let oldest = height.saturating_sub(self.max_anchor_depth);
+            
+            // Raw hashes can exceed the scalar field, so proofs carry their digest
+            if (oldest..=height).rev().any(|h| public_input_digest(&wasm::block_hash(h)) == *anchor) {
+                Ok(())
+            } else {
+                Err("Stale proof anchor".into())
+            }
+        }
+        
+        /// Nullifier, proof, anchor and freshness checks for a mint; returns the
+        /// proof cache key for the caller to record
+        fn verify_mint_proof(
+            &self,
+            proof_id: [u8; 32],
+            proof: &ZkMintProof,
+            packet: &PoEPacket,
+            nullifier_siblings: Option<&[[u8; 32]]>,
+        ) -> Result<[u8; 32], String> {
+            self.check_nullifier(proof_id, nullifier_siblings)?;
+            let cache_key = self.verify_zk_proof_cached(proof_id, proof, packet)?;
+            self.verify_proof_anchor(proof)?;
+            self.verify_proof_time(proof)?;
+            self.verify_proof_classification(proof, packet)?;
+            Ok(cache_key)
+        }
+        
+        /// Require the proof to commit to the device's current region and
+        /// classification version. Kept outside the proof cache since a
+        /// reclassification must invalidate proofs that were already verified.
+        fn verify_proof_classification(&self, proof: &ZkMintProof, packet: &PoEPacket) -> Result<(), String> {
+            let classification = proof.public_inputs
+                .get(PUBLIC_INPUT_CLASSIFICATION)
+                .ok_or("Missing proof classification")?;
+            
+            let region = self.device_region.get(&packet.device_id).ok_or("Device not registered")?;
+            let version = self.device_classification_version.get(&packet.device_id).unwrap_or(0);
+            if *classification != classification_digest(region, version) {
+                return Err("Proof against outdated device classification".into());
+            }
+            
+            Ok(())
+        }
+        
+        /// Require the proof's current time (the circuit's freshness reference) to be
+        /// within `proof_time_tolerance_ms` of block time
+        fn verify_proof_time(&self, proof: &ZkMintProof) -> Result<(), String> {
+            let current_time = proof.public_inputs
+                .get(PUBLIC_INPUT_CURRENT_TIME)
+                .and_then(public_input_to_u64)
+                .ok_or("Missing proof time")?;
+            
+            let block_time_ms = wasm::block_timestamp().saturating_mul(1_000);
+            if current_time.abs_diff(block_time_ms) > self.proof_time_tolerance_ms {
+                return Err("Proof time out of tolerance".into());
+            }
+            
+            // A prover picking its own window could accept arbitrarily old readings
+            let max_age = proof.public_inputs
+                .get(PUBLIC_INPUT_MAX_AGE)
+                .ok_or("Missing proof max age")?;
+            if *max_age != public_input_from_u64(self.max_proof_age_ms) {
+                return Err("Proof max age doesn't match policy".into());
+            }
+            
             Ok(())
         }
         
-        fn verify_utxo_payment(&self, utxo_proof: [u8; 32]) -> Result<(), String> {
+        /// Current (window start, count) for an oracle, rolling over expired windows
+        fn oracle_window_count(&self, oracle_id: &[u8; 32]) -> (u64, u32) {
+            let now = wasm::block_timestamp();
+            match self.oracle_mint_counts.get(oracle_id) {
+                Some((start, count)) if now < start.saturating_add(self.oracle_rate_window) => {
+                    (start, count)
+                }
+                _ => (now, 0),
+            }
+        }
+        
+        /// Reject a spent nullifier; in accumulator mode the caller must prove
+        /// non-membership against the current tree root
+        fn check_nullifier(
+            &self,
+            nullifier: [u8; 32],
+            siblings: Option<&[[u8; 32]]>,
+        ) -> Result<(), String> {
+            if self.proof_nullifiers.contains_key(&nullifier) {
+                return Err("Proof already used".into());
+            }
+            
+            if !self.nullifier_accumulator_enabled {
+                return Ok(());
+            }
+            
+            let siblings = siblings.ok_or("Non-membership proof required")?;
+            if siblings.len() != NULLIFIER_TREE_DEPTH {
+                return Err("Invalid non-membership proof length".into());
+            }
+            
+            // Non-membership: the nullifier's slot is still the empty leaf
+            if Self::nullifier_tree_root(&nullifier, [0u8; 32], siblings) != self.nullifier_root {
+                return Err("Proof already used".into());
+            }
+            
+            Ok(())
+        }
+        
+        /// Record a nullifier after a successful mint (assumes `check_nullifier` passed)
+        fn spend_nullifier(&mut self, nullifier: [u8; 32], siblings: Option<&[[u8; 32]]>) {
+            match siblings {
+                Some(siblings) if self.nullifier_accumulator_enabled => {
+                    // Same path, with the slot now holding the nullifier
+                    self.nullifier_root = Self::nullifier_tree_root(&nullifier, nullifier, siblings);
+                }
+                _ => {
+                    self.proof_nullifiers.insert(nullifier, true);
+                }
+            }
+        }
+        
+        /// Fold a leaf up the sparse Merkle tree; siblings are ordered leaf to root
+        /// and the key's bits (LSB first from the leaf) pick left or right
+        fn nullifier_tree_root(key: &[u8; 32], leaf: [u8; 32], siblings: &[[u8; 32]]) -> [u8; 32] {
+            let mut node = leaf;
+            for (level, sibling) in siblings.iter().enumerate() {
+                let bit = NULLIFIER_TREE_DEPTH - 1 - level;
+                let mut preimage = [0u8; 64];
+                if (key[bit / 8] >> (7 - bit % 8)) & 1 == 0 {
+                    preimage[..32].copy_from_slice(&node);
+                    preimage[32..].copy_from_slice(sibling);
+                } else {
+                    preimage[..32].copy_from_slice(sibling);
+                    preimage[32..].copy_from_slice(&node);
+                }
+                node = sha256(&preimage);
+            }
+            node
+        }
+        
+        /// Root of a nullifier tree with every leaf empty
+        fn empty_nullifier_root() -> [u8; 32] {
+            let mut node = [0u8; 32];
+            for _ in 0..NULLIFIER_TREE_DEPTH {
+                let mut preimage = [0u8; 64];
+                preimage[..32].copy_from_slice(&node);
+                preimage[32..].copy_from_slice(&node);
+                node = sha256(&preimage);
+            }
+            node
+        }
+        
+        fn proof_id(proof: &ZkMintProof) -> [u8; 32] {
+            let mut preimage = proof.vk_hash.to_vec();
+            for input in &proof.public_inputs {
+                preimage.extend_from_slice(input);
+            }
+            preimage.extend_from_slice(&proof.proof);
+            sha256(&preimage)
+        }
+        
+        /// Verify a proof, skipping the work if it already passed recently.
+        /// Returns its cache key; nothing is written until `remember_verified_proof`.
+        fn verify_zk_proof_cached(
+            &self,
+            proof_id: [u8; 32],
+            proof: &ZkMintProof,
+            packet: &PoEPacket,
+        ) -> Result<[u8; 32], String> {
+            // The verdict depends on the packet too, so cache per (proof, packet)
+            let mut preimage = proof_id.to_vec();
+            preimage.extend_from_slice(&packet.device_id);
+            preimage.extend_from_slice(&packet.energy_wh.to_be_bytes());
+            preimage.extend_from_slice(&packet.timestamp.to_be_bytes());
+            let cache_key = sha256(&preimage);
+            
+            if !self.verified_proof_cache.contains_key(&cache_key) {
+                self.verify_zk_proof(proof, packet)?;
+            }
+            Ok(cache_key)
+        }
+        
+        /// Cache a verified proof, or refresh its recency if already cached
+        fn remember_verified_proof(&mut self, cache_key: [u8; 32]) {
+            if self.verified_proof_cache.contains_key(&cache_key) {
+                self.proof_cache_order.retain(|key| *key != cache_key);
+                self.proof_cache_order.push(cache_key);
+                return;
+            }
+            
+            if self.proof_cache_capacity == 0 {
+                return;
+            }
+            
+            while self.proof_cache_order.len() >= self.proof_cache_capacity as usize {
+                let oldest = self.proof_cache_order.remove(0);
+                self.verified_proof_cache.remove(&oldest);
+            }
+            self.verified_proof_cache.insert(cache_key, true);
+            self.proof_cache_order.push(cache_key);
+        }
+        
+        fn verify_utxo_payment(&self, _utxo_proof: [u8; 32]) -> Result<(), String> {
             // Verify UTXO payment exists and is confirmed
             // This would connect to BitcoinOS UTXO verifier contract
             
-            // For now, just store commitment
-            self.utxo_commitments.push(utxo_proof);
-            
+            // For now, accept it; `execute_mint` stores the commitment once the mint succeeds
             Ok(())
         }
         
@@ -296,6 +2759,43 @@ pub mod poe_zkbtc_minter {
                 return Err("Invalid burn verification key".into());
             }
             
+            self.verify_groth16(proof)
+        }
+        
+        /// Run the Groth16 pairing check against the key registered for `vk_hash`.
+        /// Public inputs use the canonical big-endian encoding (see `public_input_digest`).
+        fn verify_groth16(&self, proof: &ZkMintProof) -> Result<(), String> {
+            // Never trust a skip flag on mainnet, whatever state says
+            if self.verification_mode == VerificationMode::TrustedSkip
+                && self.network != Network::Mainnet
+            {
+                return Ok(());
+            }
+            
+            let vk_bytes = self.verifying_keys
+                .get(&proof.vk_hash)
+                .ok_or("No verifying key registered")?;
+            let vk = VerifyingKey::<Bls12>::read(&vk_bytes[..])
+                .map_err(|_| "Malformed verifying key")?;
+            let pvk = prepare_verifying_key(&vk);
+            
+            let groth16_proof = Proof::<Bls12>::read(&proof.proof[..])
+                .map_err(|_| "Malformed proof")?;
+            
+            let inputs = proof.public_inputs
+                .iter()
+                .map(|input| {
+                    // `Scalar::from_bytes` takes little-endian bytes
+                    let mut le = *input;
+                    le.reverse();
+                    Option::<Scalar>::from(Scalar::from_bytes(&le))
+                        .ok_or("Public input out of field range")
+                })
+                .collect::<Result<Vec<Scalar>, _>>()?;
+            
+            verify_proof(&pvk, &groth16_proof, &inputs)
+                .map_err(|_| "Proof verification failed")?;
+            
             Ok(())
         }
     }
@@ -2,8 +2,65 @@
 //! Verifies Bitcoin, Litecoin, Cardano payments
 
 use charms_sdk::prelude::*;
-use bitcoin::{Txid, Transaction};
-use cardano_serialization::{Address, Value};
+use bitcoin::{consensus::deserialize, Txid, Transaction};
+use cardano_serialization::{hash_transaction, Address, TransactionBody, Value};
+
+/// Default time a shallow payment has to reach its chain's depth (seconds)
+pub const DEFAULT_CONFIRMATION_WINDOW: u64 = 86_400;
+
+/// Split a relayer proof into its leading confirmation depth and the payload.
+/// The depth is as reported by the relayer, not derived from block headers, so
+/// only registered relayers may submit proofs.
+fn split_confirmations(proof: &[u8]) -> Result<(u32, &[u8]), String> {
+    if proof.len() < 4 {
+        return Err("Proof missing confirmation depth".into());
+    }
+    let (depth, payload) = proof.split_at(4);
+    Ok((u32::from_be_bytes(depth.try_into().unwrap()), payload))
+}
+
+/// Check a Bitcoin-format transaction proof pays `expected_amount` to the
+/// output script `expected_recipient` (hex); returns the reported depth
+fn verify_bitcoin_style_payment(
+    chain: &str,
+    txid: &[u8; 32],
+    output_index: u32,
+    expected_amount: u64,
+    expected_recipient: &str,
+    merkle_proof: &[u8],
+) -> Result<u32, String> {
+    let (confirmations, raw_tx) = split_confirmations(merkle_proof)?;
+    
+    let tx: Transaction = deserialize(raw_tx)
+        .map_err(|e| format!("Malformed {} transaction: {}", chain, e))?;
+    
+    // Txids are displayed (and passed in) byte-reversed
+    let computed: Txid = tx.txid();
+    let mut computed_bytes: [u8; 32] = computed.as_ref().try_into()
+        .map_err(|_| format!("Invalid {} txid length", chain))?;
+    computed_bytes.reverse();
+    if computed_bytes != *txid {
+        return Err(format!("{} transaction does not match txid", chain));
+    }
+    
+    let output = tx.output
+        .get(output_index as usize)
+        .ok_or_else(|| format!("{} output index out of range", chain))?;
+    
+    let expected_script = hex::decode(expected_recipient)
+        .map_err(|_| format!("Invalid {} recipient script hex", chain))?;
+    if output.script_pubkey.as_bytes() != expected_script.as_slice() {
+        return Err(format!("{} output does not pay the expected recipient", chain));
+    }
+    if output.value != expected_amount {
+        return Err(format!(
+            "{} output pays {}, expected {}",
+            chain, output.value, expected_amount
+        ));
+    }
+    
+    Ok(confirmations)
+}
 
 #[wasm::contract]
 pub mod utxo_verifier {
@@ -20,31 +77,69 @@ pub mod utxo_verifier {
         pub block_hash: [u8; 32],
     }
     
+    /// A payment seen below its chain's confirmation depth, awaiting re-verification
+    #[derive(Debug, Clone, Encode, Decode)]
+    pub struct PendingPayment {
+        pub payment: UTXOPayment,
+        pub first_seen: u64,
+    }
+    
     #[contract(state)]
     pub struct UTXOVerifier {
+        #[state]
+        pub admin: String,
+        
         #[state]
         pub verified_payments: Map<[u8; 32], UTXOPayment>,
         
         #[state]
         pub rpc_endpoints: Map<String, String>, // chain -> RPC URL
+        
+        #[state]
+        pub revoked_payments: Map<[u8; 32], String>, // txid -> revocation reason
+        
+        #[state]
+        pub min_confirmations: Map<String, u32>, // chain -> depth required before storing
+        
+        #[state]
+        pub pending_confirmation: Map<[u8; 32], PendingPayment>, // txid -> shallow payment
+        
+        #[state]
+        pub confirmation_window: u64, // seconds a pending payment may take to deepen
+        
+        #[state]
+        pub relayers: Map<String, bool>, // accounts trusted to report confirmation depth
     }
     
     #[contract(impl)]
     impl UTXOVerifier {
         #[constructor]
-        pub fn new() -> Self {
+        pub fn new(admin: String) -> Self {
+            assert!(!admin.is_empty(), "Admin must not be empty");
+            
             let mut endpoints = Map::new();
             endpoints.insert("bitcoin".into(), "https://blockstream.info/api".into());
             endpoints.insert("litecoin".into(), "https://blockchair.com/litecoin".into());
             endpoints.insert("cardano".into(), "https://cardano-mainnet.blockfrost.io".into());
             
+            let mut min_confirmations = Map::new();
+            min_confirmations.insert("bitcoin".into(), 6);
+            min_confirmations.insert("litecoin".into(), 12);
+            min_confirmations.insert("cardano".into(), 15);
+            
             Self {
+                admin,
                 verified_payments: Map::new(),
                 rpc_endpoints: endpoints,
+                revoked_payments: Map::new(),
+                min_confirmations,
+                pending_confirmation: Map::new(),
+                confirmation_window: DEFAULT_CONFIRMATION_WINDOW,
+                relayers: Map::new(),
             }
         }
         
-        /// Verify a UTXO payment from any supported chain
+        /// Verify a UTXO payment from any supported chain (registered relayers only)
         #[message]
         pub fn verify_utxo_payment(
             &mut self,
@@ -55,52 +150,236 @@ pub mod utxo_verifier {
             expected_recipient: String,
             merkle_proof: Vec<u8>,
         ) -> Result<bool, String> {
+            self.ensure_relayer()?;
+            
             // Convert txid
             let txid = hex::decode(txid_hex)
                 .map_err(|_| "Invalid txid hex")?
                 .try_into()
                 .map_err(|_| "Invalid txid length")?;
             
-            // Check if already verified
-            if self.verified_payments.contains_key(&txid) {
-                return Ok(true);
+            self.verify_and_record(
+                chain,
+                txid,
+                output_index,
+                expected_amount,
+                expected_recipient,
+                &merkle_proof,
+            ).await
+        }
+        
+        /// Re-check a pending payment against a fresh relayer proof, storing it
+        /// once deep enough. Called by relayers as confirmations accrue.
+        #[message]
+        pub fn reverify_pending(&mut self, txid: [u8; 32], merkle_proof: Vec<u8>) -> Result<bool, String> {
+            self.ensure_relayer()?;
+            
+            let pending = self.pending_confirmation
+                .get(&txid)
+                .ok_or("Payment not pending")?;
+            let payment = pending.payment;
+            
+            self.verify_and_record(
+                payment.chain,
+                txid,
+                payment.output_index,
+                payment.amount,
+                payment.recipient,
+                &merkle_proof,
+            ).await
+        }
+        
+        /// Drop pending payments that failed to deepen within the confirmation
+        /// window, e.g. dropped from the mempool. Returns how many expired.
+        #[message]
+        pub fn expire_pending(&mut self) -> Result<u32, String> {
+            let now = wasm::block_timestamp();
+            let expired: Vec<([u8; 32], String)> = self.pending_confirmation
+                .iter()
+                .filter(|(_, pending)| now.saturating_sub(pending.first_seen) > self.confirmation_window)
+                .map(|(txid, pending)| (txid, pending.payment.chain))
+                .collect();
+            
+            for (txid, chain) in &expired {
+                self.pending_confirmation.remove(txid);
+                wasm::emit_event("PaymentExpired", &(*txid, chain.clone()));
+            }
+            
+            Ok(expired.len() as u32)
+        }
+        
+        /// Set how long a shallow payment may take to reach its depth (admin only)
+        #[message]
+        pub fn set_confirmation_window(&mut self, window: u64) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if window == 0 {
+                return Err("Confirmation window must be non-zero".into());
+            }
+            
+            self.confirmation_window = window;
+            wasm::emit_event("ConfirmationWindowUpdated", &window);
+            Ok(())
+        }
+        
+        /// Shallow payment awaiting re-verification, if any
+        #[view]
+        pub fn get_pending_payment(&self, txid: [u8; 32]) -> Option<PendingPayment> {
+            self.pending_confirmation.get(&txid)
+        }
+        
+        /// Verify a payment and store it once the relayer-reported depth reaches
+        /// the chain's minimum; shallower payments are tracked as pending until
+        /// they deepen or expire
+        async fn verify_and_record(
+            &mut self,
+            chain: String,
+            txid: [u8; 32],
+            output_index: u32,
+            expected_amount: u64,
+            expected_recipient: String,
+            merkle_proof: &[u8],
+        ) -> Result<bool, String> {
+            if self.revoked_payments.contains_key(&txid) {
+                return Err("Payment revoked".into());
             }
             
-            // Verify based on chain
-            let verified = match chain.as_str() {
+            let min_confirmations = self.min_confirmations
+                .get(&chain)
+                .ok_or("Unsupported chain")?;
+            
+            // Verify based on chain; helpers check the output and return the
+            // confirmation depth the relayer reported
+            let confirmations = match chain.as_str() {
                 "bitcoin" => self.verify_bitcoin_payment(
-                    &txid, output_index, expected_amount, &expected_recipient, &merkle_proof
+                    &txid, output_index, expected_amount, &expected_recipient, merkle_proof
                 ).await?,
                 
                 "cardano" => self.verify_cardano_payment(
-                    &txid, output_index, expected_amount, &expected_recipient, &merkle_proof
+                    &txid, output_index, expected_amount, &expected_recipient, merkle_proof
                 ).await?,
                 
                 "litecoin" => self.verify_litecoin_payment(
-                    &txid, output_index, expected_amount, &expected_recipient, &merkle_proof
+                    &txid, output_index, expected_amount, &expected_recipient, merkle_proof
                 ).await?,
                 
                 _ => return Err("Unsupported chain".into()),
             };
             
-            if verified {
-                // Store verified payment
-                let payment = UTXOPayment {
-                    chain,
-                    txid,
-                    output_index,
-                    amount: expected_amount,
-                    recipient: expected_recipient,
-                    confirmations: 6, // Assume confirmed
-                    block_hash: [0u8; 32], // Would be actual block hash
-                };
-                
-                self.verified_payments.insert(txid, payment);
+            // Too shallow: track it so relayers can re-verify as it deepens
+            if confirmations < min_confirmations {
+                let first_seen = self.pending_confirmation
+                    .get(&txid)
+                    .map_or(wasm::block_timestamp(), |pending| pending.first_seen);
+                self.pending_confirmation.insert(txid, PendingPayment {
+                    payment: UTXOPayment {
+                        chain,
+                        txid,
+                        output_index,
+                        amount: expected_amount,
+                        recipient: expected_recipient,
+                        confirmations,
+                        block_hash: [0u8; 32],
+                    },
+                    first_seen,
+                });
+                return Ok(false);
+            }
+            
+            // Re-verification may only deepen what is already recorded
+            if let Some(existing) = self.verified_payments.get(&txid) {
+                if confirmations < existing.confirmations {
+                    return Err("Shallower than recorded confirmations".into());
+                }
+            }
+            
+            // Store verified payment
+            let payment = UTXOPayment {
+                chain,
+                txid,
+                output_index,
+                amount: expected_amount,
+                recipient: expected_recipient,
+                confirmations,
+                block_hash: [0u8; 32], // Would be actual block hash
+            };
+            
+            self.verified_payments.insert(txid, payment);
+            self.pending_confirmation.remove(&txid);
+            
+            Ok(true)
+        }
+        
+        /// Revoke a fraudulent verified payment (admin only)
+        #[message]
+        pub fn revoke_payment(&mut self, txid: [u8; 32], reason: String) -> Result<UTXOPayment, String> {
+            self.ensure_admin()?;
+            
+            let payment = self.verified_payments
+                .remove(&txid)
+                .ok_or("Payment not verified")?;
+            self.revoked_payments.insert(txid, reason.clone());
+            
+            // Minter/vault relayers watch this to flag dependent mints and backing
+            wasm::emit_event("PaymentRevoked", &(txid, payment.chain.clone(), payment.amount, reason));
+            
+            Ok(payment)
+        }
+        
+        /// Set the confirmation depth a chain's payments need (admin only)
+        #[message]
+        pub fn set_min_confirmations(&mut self, chain: String, confirmations: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if !self.rpc_endpoints.contains_key(&chain) {
+                return Err("Unsupported chain".into());
+            }
+            
+            self.min_confirmations.insert(chain.clone(), confirmations);
+            wasm::emit_event("MinConfirmationsUpdated", &(chain, confirmations));
+            Ok(())
+        }
+        
+        /// Allow or disallow an account to submit payment proofs (admin only)
+        #[message]
+        pub fn set_relayer(&mut self, relayer: String, enabled: bool) -> Result<(), String> {
+            self.ensure_admin()?;
+            
+            if relayer.is_empty() {
+                return Err("Relayer must not be empty".into());
             }
             
-            Ok(verified)
+            if enabled {
+                self.relayers.insert(relayer.clone(), true);
+            } else {
+                self.relayers.remove(&relayer);
+            }
+            wasm::emit_event("RelayerUpdated", &(relayer, enabled));
+            Ok(())
+        }
+        
+        /// Verified (and not revoked) payment for a txid, queried by other contracts
+        #[view]
+        pub fn get_payment(&self, txid: [u8; 32]) -> Option<UTXOPayment> {
+            self.verified_payments.get(&txid)
         }
         
+        fn ensure_admin(&self) -> Result<(), String> {
+            if wasm::caller() != self.admin {
+                return Err("Caller is not admin".into());
+            }
+            Ok(())
+        }
+        
+        fn ensure_relayer(&self) -> Result<(), String> {
+            if !self.relayers.get(&wasm::caller()).unwrap_or(false) {
+                return Err("Caller is not a registered relayer".into());
+            }
+            Ok(())
+        }
+        
+        /// The proof is the confirmation depth (4 bytes, big-endian) followed by
+        /// the raw transaction; `expected_recipient` is the output script in hex.
         async fn verify_bitcoin_payment(
             &self,
             txid: &[u8; 32],
@@ -108,18 +387,314 @@ pub mod utxo_verifier {
             expected_amount: u64,
             expected_recipient: &str,
             merkle_proof: &[u8],
-        ) -> Result<bool, String> {
-            // In production: Connect to Bitcoin RPC or use SPV proof
+        ) -> Result<u32, String> {
+            verify_bitcoin_style_payment(
+                "Bitcoin", txid, output_index, expected_amount, expected_recipient, merkle_proof
+            )
+        }
+        
+        /// Litecoin shares Bitcoin's transaction format and proof layout
+        async fn verify_litecoin_payment(
+            &self,
+            txid: &[u8; 32],
+            output_index: u32,
+            expected_amount: u64,
+            expected_recipient: &str,
+            merkle_proof: &[u8],
+        ) -> Result<u32, String> {
+            verify_bitcoin_style_payment(
+                "Litecoin", txid, output_index, expected_amount, expected_recipient, merkle_proof
+            )
+        }
+        
+        /// The proof is the confirmation depth (4 bytes, big-endian) followed by
+        /// the CBOR transaction body; `expected_recipient` is a bech32 address
+        /// and `expected_amount` is in lovelace.
+        async fn verify_cardano_payment(
+            &self,
+            txid: &[u8; 32],
+            output_index: u32,
+            expected_amount: u64,
+            expected_recipient: &str,
+            merkle_proof: &[u8],
+        ) -> Result<u32, String> {
+            let (confirmations, body_bytes) = split_confirmations(merkle_proof)?;
             
-            // For demo, accept any non-zero proof
-            if merkle_proof.is_empty() {
-                return Ok(false);
+            let body = TransactionBody::from_bytes(body_bytes.to_vec())
+                .map_err(|e| format!("Malformed Cardano transaction body: {:?}", e))?;
+            
+            if hash_transaction(&body).to_bytes() != txid.to_vec() {
+                return Err("Cardano transaction does not match txid".into());
             }
             
-            // Simulate verification
-            Ok(true)
+            let outputs = body.outputs();
+            if output_index as usize >= outputs.len() {
+                return Err("Cardano output index out of range".into());
+            }
+            let output = outputs.get(output_index as usize);
+            
+            let recipient = Address::from_bech32(expected_recipient)
+                .map_err(|e| format!("Invalid Cardano address: {:?}", e))?;
+            if output.address().to_bytes() != recipient.to_bytes() {
+                return Err("Cardano output does not pay the expected recipient".into());
+            }
+            let amount: Value = output.amount();
+            if u64::from(amount.coin()) != expected_amount {
+                return Err(format!(
+                    "Cardano output pays {} lovelace, expected {}",
+                    u64::from(amount.coin()),
+                    expected_amount
+                ));
+            }
+            
+            Ok(confirmations)
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utxo_verifier::*;
+    use bitcoin::consensus::serialize;
+    use bitcoin::{PackedLockTime, Script, Transaction, TxIn, TxOut};
+    use cardano_serialization::{
+        hash_transaction, Address, Coin, Ed25519KeyHash, EnterpriseAddress, StakeCredential,
+        TransactionBody, TransactionInputs, TransactionOutput, TransactionOutputs, Value,
+    };
+    use charms_sdk::testing;
+    
+    const ADMIN: &str = "dao";
+    const RELAYER: &str = "relayer";
+    const SCRIPT: [u8; 22] = [0x00, 0x14, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7];
+    
+    fn verifier() -> UTXOVerifier {
+        testing::reset();
+        testing::set_caller(ADMIN);
+        let mut verifier = UTXOVerifier::new(ADMIN.into());
+        verifier.set_relayer(RELAYER.into(), true).unwrap();
+        testing::set_caller(RELAYER);
+        verifier
+    }
+    
+    /// Bitcoin-format transaction paying `value` to `SCRIPT`, with its display-order txid
+    fn btc_payment(value: u64) -> (Transaction, [u8; 32]) {
+        let tx = Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: vec![TxIn::default()],
+            output: vec![TxOut { value, script_pubkey: Script::from(SCRIPT.to_vec()) }],
+        };
+        let mut txid: [u8; 32] = tx.txid().as_ref().try_into().unwrap();
+        txid.reverse();
+        (tx, txid)
+    }
+    
+    fn ada_address(key_seed: u8) -> Address {
+        let credential = StakeCredential::from_keyhash(&Ed25519KeyHash::from([key_seed; 28]));
+        EnterpriseAddress::new(1, &credential).to_address()
+    }
+    
+    /// Cardano transaction body paying `lovelace` to `ada_address(3)`; returns
+    /// its bytes, txid and the address in bech32
+    fn ada_payment(lovelace: u64) -> (Vec<u8>, [u8; 32], String) {
+        let address = ada_address(3);
+        
+        let mut outputs = TransactionOutputs::new();
+        outputs.add(&TransactionOutput::new(&address, &Value::new(&Coin::from(lovelace))));
+        let body = TransactionBody::new(&TransactionInputs::new(), &outputs, &Coin::from(170_000u64), None);
+        
+        let txid: [u8; 32] = hash_transaction(&body).to_bytes().try_into().unwrap();
+        (body.to_bytes(), txid, address.to_bech32(None).unwrap())
+    }
+    
+    fn submit(
+        verifier: &mut UTXOVerifier,
+        chain: &str,
+        txid: [u8; 32],
+        amount: u64,
+        recipient: &str,
+        proof: Vec<u8>,
+    ) -> Result<bool, String> {
+        verifier.verify_utxo_payment(chain.into(), hex::encode(txid), 0, amount, recipient.into(), proof)
+    }
+    
+    /// Relayer proof: reported depth followed by the payload
+    fn relayer_proof(confirmations: u32, payload: Vec<u8>) -> Vec<u8> {
+        let mut proof = confirmations.to_be_bytes().to_vec();
+        proof.extend(payload);
+        proof
+    }
+    
+    #[test]
+    fn only_registered_relayers_submit_proofs() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(50_000);
+        let proof = relayer_proof(6, serialize(&tx));
+        
+        testing::set_caller("anyone");
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, &hex::encode(SCRIPT), proof.clone()),
+            Err("Caller is not a registered relayer".into())
+        );
+        assert_eq!(
+            verifier.reverify_pending(txid, proof.clone()),
+            Err("Caller is not a registered relayer".into())
+        );
+        
+        testing::set_caller(RELAYER);
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, &hex::encode(SCRIPT), proof),
+            Ok(true)
+        );
+        assert_eq!(verifier.get_payment(txid).unwrap().amount, 50_000);
+    }
+    
+    #[test]
+    fn bitcoin_mismatches_are_rejected_with_reasons() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(50_000);
+        let script = hex::encode(SCRIPT);
+        
+        // A bare depth claim no longer verifies anything
+        assert!(submit(&mut verifier, "bitcoin", txid, 50_000, &script, relayer_proof(6, vec![])).is_err());
+        
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 90_000, &script, relayer_proof(6, serialize(&tx))),
+            Err("Bitcoin output pays 50000, expected 90000".into())
+        );
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, "0014aa", relayer_proof(6, serialize(&tx))),
+            Err("Bitcoin output does not pay the expected recipient".into())
+        );
+        assert!(verifier.get_payment(txid).is_none());
+        assert!(verifier.get_pending_payment(txid).is_none());
+    }
+    
+    #[test]
+    fn litecoin_payment_verifies_or_reports_mismatch() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(2_500_000);
+        let script = hex::encode(SCRIPT);
+        
+        assert_eq!(
+            submit(&mut verifier, "litecoin", txid, 2_400_000, &script, relayer_proof(12, serialize(&tx))),
+            Err("Litecoin output pays 2500000, expected 2400000".into())
+        );
+        assert_eq!(
+            submit(&mut verifier, "litecoin", [0u8; 32], 2_500_000, &script, relayer_proof(12, serialize(&tx))),
+            Err("Litecoin transaction does not match txid".into())
+        );
+        
+        // Shallow first, then stored once deep enough
+        assert_eq!(
+            submit(&mut verifier, "litecoin", txid, 2_500_000, &script, relayer_proof(3, serialize(&tx))),
+            Ok(false)
+        );
+        assert!(verifier.get_pending_payment(txid).is_some());
+        assert_eq!(verifier.reverify_pending(txid, relayer_proof(12, serialize(&tx))), Ok(true));
+        assert_eq!(verifier.get_payment(txid).unwrap().confirmations, 12);
+    }
+    
+    #[test]
+    fn cardano_payment_verifies_or_reports_mismatch() {
+        let mut verifier = verifier();
+        let (body, txid, address) = ada_payment(2_000_000);
+        
+        assert_eq!(
+            submit(&mut verifier, "cardano", txid, 3_000_000, &address, relayer_proof(15, body.clone())),
+            Err("Cardano output pays 2000000 lovelace, expected 3000000".into())
+        );
+        
+        let other = ada_address(4).to_bech32(None).unwrap();
+        assert_eq!(
+            submit(&mut verifier, "cardano", txid, 2_000_000, &other, relayer_proof(15, body.clone())),
+            Err("Cardano output does not pay the expected recipient".into())
+        );
+        
+        assert_eq!(
+            submit(&mut verifier, "cardano", txid, 2_000_000, &address, relayer_proof(15, body)),
+            Ok(true)
+        );
+    }
+    
+    #[test]
+    fn shallow_payment_is_stored_once_it_deepens() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(50_000);
+        let script = hex::encode(SCRIPT);
+        testing::set_block_timestamp(1_000);
+        
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, &script, relayer_proof(2, serialize(&tx))),
+            Ok(false)
+        );
+        assert!(verifier.get_payment(txid).is_none());
+        
+        // Still shallow: the window keeps counting from the first sighting
+        testing::set_block_timestamp(2_000);
+        assert_eq!(verifier.reverify_pending(txid, relayer_proof(4, serialize(&tx))), Ok(false));
+        let pending = verifier.get_pending_payment(txid).unwrap();
+        assert_eq!((pending.first_seen, pending.payment.confirmations), (1_000, 4));
+        
+        assert_eq!(verifier.reverify_pending(txid, relayer_proof(6, serialize(&tx))), Ok(true));
+        assert_eq!(verifier.get_payment(txid).unwrap().confirmations, 6);
+        assert!(verifier.get_pending_payment(txid).is_none());
+        assert_eq!(verifier.expire_pending(), Ok(0));
+    }
+    
+    #[test]
+    fn shallow_payment_expires_if_it_never_deepens() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(50_000);
+        let script = hex::encode(SCRIPT);
+        
+        testing::set_caller(ADMIN);
+        verifier.set_confirmation_window(3_600).unwrap();
+        testing::set_caller(RELAYER);
+        
+        testing::set_block_timestamp(1_000);
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, &script, relayer_proof(1, serialize(&tx))),
+            Ok(false)
+        );
+        
+        testing::set_block_timestamp(1_000 + 3_600);
+        assert_eq!(verifier.expire_pending(), Ok(0));
+        
+        testing::set_block_timestamp(1_000 + 3_601);
+        assert_eq!(verifier.expire_pending(), Ok(1));
+        assert!(verifier.get_pending_payment(txid).is_none());
+        assert!(verifier.get_payment(txid).is_none());
+        assert_eq!(
+            verifier.reverify_pending(txid, relayer_proof(6, serialize(&tx))),
+            Err("Payment not pending".into())
+        );
+    }
+    
+    #[test]
+    fn revoked_payment_is_removed_and_cannot_be_resubmitted() {
+        let mut verifier = verifier();
+        let (tx, txid) = btc_payment(50_000);
+        let script = hex::encode(SCRIPT);
+        submit(&mut verifier, "bitcoin", txid, 50_000, &script, relayer_proof(6, serialize(&tx))).unwrap();
+        
+        assert_eq!(
+            verifier.revoke_payment(txid, "fake SPV proof".into()),
+            Err("Caller is not admin".into())
+        );
+        
+        testing::set_caller(ADMIN);
+        assert_eq!(verifier.revoke_payment(txid, "fake SPV proof".into()).unwrap().amount, 50_000);
+        assert!(verifier.get_payment(txid).is_none());
+        assert_eq!(
+            verifier.revoke_payment(txid, "fake SPV proof".into()),
+            Err("Payment not verified".into())
+        );
         
-        // Similar methods for Cardano and Litecoin...
+        testing::set_caller(RELAYER);
+        assert_eq!(
+            submit(&mut verifier, "bitcoin", txid, 50_000, &script, relayer_proof(6, serialize(&tx))),
+            Err("Payment revoked".into())
+        );
     }
 }
@@ -0,0 +1,30 @@
+//! Health endpoint reporting per-source connection state
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+
+use crate::{ConnectionState, OracleService};
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    /// True when every known source is connected
+    pub healthy: bool,
+    /// Connection state per source id
+    pub sources: HashMap<String, ConnectionState>,
+    /// Verified packets held locally
+    pub verified_count: usize,
+}
+
+/// `GET /health`
+pub fn health_routes(service: Arc<OracleService>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .with_state(service)
+}
+
+async fn health(State(service): State<Arc<OracleService>>) -> Json<HealthReport> {
+    Json(service.health().await)
+}
@@ -0,0 +1,5 @@
+//! HTTP API exposed by the oracle service
+
+pub mod health;
+
+pub use health::{health_routes, HealthReport};
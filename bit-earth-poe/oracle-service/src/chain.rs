@@ -0,0 +1,22 @@
+//! Chain height as seen by the oracle
+//! Verified readings record the block they were submitted at through `BlockSource`
+
+use async_trait::async_trait;
+use web3::{transports::Http, Web3};
+
+/// Reports the current block number of the chain readings are submitted to
+#[async_trait]
+pub trait BlockSource: Send + Sync {
+    async fn block_number(&self) -> Result<u64, String>;
+}
+
+#[async_trait]
+impl BlockSource for Web3<Http> {
+    async fn block_number(&self) -> Result<u64, String> {
+        self.eth()
+            .block_number()
+            .await
+            .map(|number| number.as_u64())
+            .map_err(|e| format!("{:?}", e))
+    }
+}
@@ -0,0 +1,35 @@
+//! Meter data sources
+//! Adapters that turn a meter's native protocol into `IoTData` readings
+
+use async_trait::async_trait;
+
+use crate::IoTData;
+
+pub mod modbus;
+pub mod mqtt;
+pub mod simulated;
+
+pub use modbus::{ModbusMeter, ModbusRegisterMap};
+pub use mqtt::MqttMeter;
+pub use simulated::SimulatedMeter;
+
+/// A source of meter readings (MQTT, Modbus-TCP, OPC-UA, simulated...)
+#[async_trait]
+pub trait MeterSource: Send {
+    /// Meter id this source reports for
+    fn meter_id(&self) -> &str;
+    
+    /// Identity of the physical connection, used to bind it to a meter id
+    fn source_id(&self) -> String;
+    
+    /// Block until the next reading is available
+    async fn next_reading(&mut self) -> Result<IoTData, String>;
+}
+
+/// Unix timestamp in milliseconds
+pub(crate) fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
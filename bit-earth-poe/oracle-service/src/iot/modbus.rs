@@ -0,0 +1,184 @@
+//! Modbus-TCP meter adapter
+//! Polls holding registers and maps them onto `IoTData`
+
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+use tokio::time::Duration;
+use tokio_modbus::client::{tcp, Context, Reader};
+use tokio_modbus::slave::Slave;
+
+use super::{now_millis, MeterSource};
+use crate::IoTData;
+
+/// Register layout of a meter: each quantity is a 32-bit big-endian value
+/// spread over two consecutive holding registers, divided by its scale
+#[derive(Debug, Clone)]
+pub struct ModbusRegisterMap {
+    pub voltage: (u16, f64),
+    pub current: (u16, f64),
+    pub power_factor: (u16, f64),
+    pub cumulative_kwh: (u16, f64),
+}
+
+impl Default for ModbusRegisterMap {
+    fn default() -> Self {
+        Self {
+            voltage: (0, 10.0),          // 0.1 V
+            current: (2, 100.0),         // 0.01 A
+            power_factor: (4, 1000.0),   // 0.001
+            cumulative_kwh: (6, 10.0),   // 0.1 kWh
+        }
+    }
+}
+
+pub struct ModbusMeter {
+    meter_id: String,
+    addr: SocketAddr,
+    unit_id: u8,
+    registers: ModbusRegisterMap,
+    poll_interval: Duration,
+    ctx: Option<Context>,
+    first: bool,
+}
+
+impl ModbusMeter {
+    pub fn new(
+        meter_id: String,
+        addr: SocketAddr,
+        unit_id: u8,
+        registers: ModbusRegisterMap,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            meter_id,
+            addr,
+            unit_id,
+            registers,
+            poll_interval,
+            ctx: None,
+            first: true,
+        }
+    }
+    
+    async fn read_scaled(&mut self, (register, scale): (u16, f64)) -> Result<f64, String> {
+        let ctx = match self.ctx.as_mut() {
+            Some(ctx) => ctx,
+            None => {
+                let ctx = tcp::connect_slave(self.addr, Slave(self.unit_id))
+                    .await
+                    .map_err(|e| format!("Modbus connect failed: {}", e))?;
+                self.ctx.insert(ctx)
+            }
+        };
+        
+        let words = match ctx.read_holding_registers(register, 2).await {
+            Ok(words) => words,
+            Err(e) => {
+                // Drop the connection so the next poll reconnects
+                self.ctx = None;
+                return Err(format!("Modbus read of register {} failed: {}", register, e));
+            }
+        };
+        
+        registers_to_value(&words, scale)
+    }
+}
+
+/// Combine two big-endian holding registers into a scaled value
+pub fn registers_to_value(words: &[u16], scale: f64) -> Result<f64, String> {
+    match words {
+        [hi, lo] => Ok((((*hi as u32) << 16) | *lo as u32) as f64 / scale),
+        _ => Err(format!("Expected 2 registers, got {}", words.len())),
+    }
+}
+
+#[async_trait]
+impl MeterSource for ModbusMeter {
+    fn meter_id(&self) -> &str {
+        &self.meter_id
+    }
+    
+    fn source_id(&self) -> String {
+        format!("modbus://{}/{}", self.addr, self.unit_id)
+    }
+    
+    async fn next_reading(&mut self) -> Result<IoTData, String> {
+        if !self.first {
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        self.first = false;
+        
+        let registers = self.registers.clone();
+        
+        Ok(IoTData {
+            meter_id: self.meter_id.clone(),
+            timestamp: now_millis(),
+            voltage: self.read_scaled(registers.voltage).await?,
+            current: self.read_scaled(registers.current).await?,
+            power_factor: self.read_scaled(registers.power_factor).await?,
+            cumulative_kwh: self.read_scaled(registers.cumulative_kwh).await?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    
+    /// Minimal Modbus-TCP server answering "read holding registers" from `registers`
+    async fn mock_server(registers: Vec<u16>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            
+            // MBAP header (7 bytes) + function, start register, register count
+            let mut request = [0u8; 12];
+            while stream.read_exact(&mut request).await.is_ok() {
+                let start = u16::from_be_bytes([request[8], request[9]]) as usize;
+                let count = u16::from_be_bytes([request[10], request[11]]) as usize;
+                
+                let mut response = request[..4].to_vec(); // transaction + protocol id
+                response.extend_from_slice(&(3 + 2 * count as u16).to_be_bytes());
+                response.extend_from_slice(&[request[6], 0x03, (2 * count) as u8]);
+                for word in &registers[start..start + count] {
+                    response.extend_from_slice(&word.to_be_bytes());
+                }
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+        
+        addr
+    }
+    
+    #[tokio::test]
+    async fn holding_registers_map_to_a_reading() {
+        // 230.5 V, 10.25 A, 0.950 pf and 123456.7 kWh in the default layout
+        let addr = mock_server(vec![0, 2_305, 0, 1_025, 0, 950, 0x0012, 0xD687]).await;
+        let mut meter = ModbusMeter::new(
+            "meter-a".into(),
+            addr,
+            1,
+            ModbusRegisterMap::default(),
+            Duration::from_secs(1),
+        );
+        
+        let reading = meter.next_reading().await.unwrap();
+        assert_eq!(reading.meter_id, "meter-a");
+        assert_eq!(reading.voltage, 230.5);
+        assert_eq!(reading.current, 10.25);
+        assert_eq!(reading.power_factor, 0.95);
+        assert_eq!(reading.cumulative_kwh, 123_456.7);
+        assert_eq!(meter.source_id(), format!("modbus://{}/1", addr));
+    }
+    
+    #[test]
+    fn value_needs_exactly_two_registers() {
+        assert_eq!(registers_to_value(&[1, 0], 1.0), Ok(65_536.0));
+        assert_eq!(registers_to_value(&[1], 1.0), Err("Expected 2 registers, got 1".into()));
+    }
+}
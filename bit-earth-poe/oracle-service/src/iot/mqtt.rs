@@ -0,0 +1,73 @@
+//! MQTT meter adapter
+//! Subscribes to a meter's topic and decodes JSON payloads into `IoTData`
+
+use async_trait::async_trait;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
+
+use super::MeterSource;
+use crate::IoTData;
+
+/// Capacity of the client's outgoing request queue
+const REQUEST_CHANNEL_CAPACITY: usize = 10;
+
+pub struct MqttMeter {
+    meter_id: String,
+    broker_url: String,
+    topic: String,
+    client: AsyncClient,
+    eventloop: EventLoop,
+}
+
+impl MqttMeter {
+    /// Connects lazily: the first `next_reading` drives the connection
+    pub fn new(meter_id: String, broker_url: &str) -> Result<Self, String> {
+        let url = format!("{}?client_id=poe-oracle-{}", broker_url, meter_id);
+        let options = MqttOptions::parse_url(url)
+            .map_err(|e| format!("Invalid MQTT broker URL {}: {}", broker_url, e))?;
+        let (client, eventloop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+        
+        Ok(Self {
+            topic: format!("meters/{}", meter_id),
+            meter_id,
+            broker_url: broker_url.to_string(),
+            client,
+            eventloop,
+        })
+    }
+}
+
+#[async_trait]
+impl MeterSource for MqttMeter {
+    fn meter_id(&self) -> &str {
+        &self.meter_id
+    }
+    
+    fn source_id(&self) -> String {
+        format!("{}/{}", self.broker_url, self.topic)
+    }
+    
+    async fn next_reading(&mut self) -> Result<IoTData, String> {
+        loop {
+            let event = self.eventloop
+                .poll()
+                .await
+                .map_err(|e| format!("MQTT connection to {} failed: {}", self.broker_url, e))?;
+            
+            match event {
+                // (Re)subscribe on every connect; the broker may not keep the session
+                Event::Incoming(Packet::ConnAck(_)) => {
+                    self.client
+                        .try_subscribe(&self.topic, QoS::AtLeastOnce)
+                        .map_err(|e| format!("MQTT subscribe to {} failed: {}", self.topic, e))?;
+                }
+                Event::Incoming(Packet::Publish(publish)) if publish.topic == self.topic => {
+                    match serde_json::from_slice::<IoTData>(&publish.payload) {
+                        Ok(reading) => return Ok(reading),
+                        Err(e) => eprintln!("Skipping malformed payload on {}: {}", self.topic, e),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Simulated meter emitting a fixed reading on an interval
+
+use async_trait::async_trait;
+use tokio::time::Duration;
+
+use super::{now_millis, MeterSource};
+use crate::IoTData;
+
+pub struct SimulatedMeter {
+    meter_id: String,
+    interval: Duration,
+    first: bool,
+}
+
+impl SimulatedMeter {
+    pub fn new(meter_id: String, interval: Duration) -> Self {
+        Self { meter_id, interval, first: true }
+    }
+}
+
+#[async_trait]
+impl MeterSource for SimulatedMeter {
+    fn meter_id(&self) -> &str {
+        &self.meter_id
+    }
+    
+    fn source_id(&self) -> String {
+        format!("simulated://{}", self.meter_id)
+    }
+    
+    async fn next_reading(&mut self) -> Result<IoTData, String> {
+        if !self.first {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.first = false;
+        
+        Ok(IoTData {
+            meter_id: self.meter_id.clone(),
+            timestamp: now_millis(),
+            voltage: 230.0,
+            current: 10.0,
+            power_factor: 0.95,
+            cumulative_kwh: 1500.5,
+        })
+    }
+}
@@ -1,11 +1,94 @@
 //! Oracle service for IoT data verification
 //! Connects to smart meters and validates PoE data
 
+mod api;
+mod chain;
+mod iot;
+mod signer;
+mod submit;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use serde::{Deserialize, Serialize};
-use web3::types::H256;
-use ed25519_dalek::{Keypair, Signer};
+use web3::{transports::Http, types::H256, Web3};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+use chain::BlockSource;
+use iot::{MeterSource, MqttMeter};
+use signer::{LocalSigner, Signer};
+use submit::{CharmsSubmitter, Submitter};
+use poe_zkbtc_minter::{packet_signing_bytes, EnergySource, PoEPacket, WH_PER_TOKEN};
+
+/// Block-number regressions up to this depth are treated as chain reorgs
+pub const DEFAULT_MAX_REORG_DEPTH: u64 = 6;
+
+/// First reconnect delay after a source drops
+pub const INITIAL_RECONNECT_BACKOFF_SECS: u64 = 1;
+
+/// Upper bound on the reconnect delay
+pub const MAX_RECONNECT_BACKOFF_SECS: u64 = 60;
+
+/// Default energy accumulated per meter before a packet is proven (one token's worth)
+pub const DEFAULT_AGGREGATION_THRESHOLD_WH: u64 = WH_PER_TOKEN;
+
+/// Attempts made to submit a verified reading before giving up
+pub const MAX_SUBMIT_ATTEMPTS: u32 = 5;
+
+/// Time allowed for one submission attempt
+pub const SUBMIT_TIMEOUT_SECS: u64 = 30;
+
+/// Default age after which unproven accumulated energy is forfeited (7 days)
+pub const DEFAULT_PENDING_ENERGY_TTL_MS: u64 = 7 * 24 * 3_600 * 1_000;
+
+/// Power factor below which a reading is flagged as a likely metering glitch
+pub const MIN_PLAUSIBLE_POWER_FACTOR: f64 = 0.05;
+
+/// Packet schema versions the oracle understands. Packets are always signed with
+/// the oracle's Ed25519 key, the only scheme the minter verifies.
+pub const SUPPORTED_SCHEMA_VERSIONS: [u16; 1] = [1];
+
+/// Opt-in gap filling: a reading that arrives after skipped intervals has its
+/// energy spread over synthesized readings, one per interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterpolationConfig {
+    /// Expected spacing of a meter's readings (ms)
+    pub interval_ms: u64,
+    /// Longest gap, in missed intervals, that is interpolated; longer gaps are flagged
+    pub max_gap_intervals: u32,
+}
+
+impl InterpolationConfig {
+    /// Intervals skipped between two readings, if noticeably more than one elapsed
+    pub fn missed_intervals(&self, previous_ms: u64, current_ms: u64) -> Option<u64> {
+        let elapsed = current_ms.saturating_sub(previous_ms);
+        if self.interval_ms == 0 || elapsed * 2 <= self.interval_ms * 3 {
+            return None;
+        }
+        
+        let intervals = (elapsed + self.interval_ms / 2) / self.interval_ms;
+        Some(intervals - 1)
+    }
+}
+
+/// Connection state of a meter source, reported by the health endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting { attempt: u32, since: u64 },
+}
+
+/// Per-meter result of the handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeterSession {
+    pub schema_version: u16,
+}
+
+impl Default for MeterSession {
+    fn default() -> Self {
+        Self { schema_version: 1 }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IoTData {
@@ -22,84 +105,559 @@ pub struct VerifiedPoE {
     pub packet: Vec<u8>,
     pub signature: [u8; 64],
     pub oracle_id: [u8; 32],
-    pub block_number: u64,
+    /// Block at submission, or `None` if the RPC was unavailable; such records
+    /// are kept and flagged for reconciliation
+    pub block_number: Option<u64>,
+    /// Monotonic per oracle, assigned when the reading is first verified
+    pub nonce: u64,
+    /// `poe_id(oracle_id, packet)`: identical for retries of the same reading
+    pub poe_id: [u8; 32],
+}
+
+/// Stable identifier of a verified reading, used to dedup retries and restarts
+pub fn poe_id(oracle_id: &[u8; 32], packet: &[u8]) -> [u8; 32] {
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(oracle_id);
+    hasher.update(packet);
+    hasher.finalize().into()
+}
+
+/// Dedup state that must survive a restart: the last nonce issued and the id of
+/// every reading already submitted
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedState {
+    last_nonce: u64,
+    submitted_poe_ids: Vec<[u8; 32]>,
 }
 
 pub struct OracleService {
-    keypair: Keypair,
+    signer: Box<dyn Signer>,
+    submitter: Box<dyn Submitter>,
     rpc_url: String,
-    verified_data: Arc<Mutex<Vec<VerifiedPoE>>>,
+    broker_url: String,
+    blocks: Box<dyn BlockSource>,
+    verified_data: Arc<Mutex<HashMap<[u8; 32], VerifiedPoE>>>, // poe id -> verified reading
+    next_nonce: Arc<Mutex<u64>>,
+    submitted_poe_ids: Arc<Mutex<HashSet<[u8; 32]>>>,
+    state_path: Option<PathBuf>,
+    sessions: Arc<Mutex<HashMap<String, MeterSession>>>,
+    provisioned_meters: Arc<Mutex<HashMap<String, String>>>, // source id -> meter id
+    last_block_number: Arc<Mutex<u64>>,
+    max_reorg_depth: u64,
+    connection_states: Arc<Mutex<HashMap<String, ConnectionState>>>, // source id -> state
+    last_cumulative_wh: Arc<Mutex<HashMap<String, u128>>>, // meter id -> last counter
+    pending_energy_wh: Arc<Mutex<HashMap<String, (u64, u64)>>>, // meter id -> (energy not yet proven, accumulating since ms)
+    meter_attributes: Arc<Mutex<HashMap<String, (EnergySource, u32)>>>, // meter id -> (source, grid id)
+    aggregation_threshold_wh: u64,
+    pending_energy_ttl_ms: u64,
+    last_reading_ms: Arc<Mutex<HashMap<String, u64>>>, // meter id -> last reading timestamp
+    interpolation: Option<InterpolationConfig>,
 }
 
 impl OracleService {
-    pub fn new(private_key: [u8; 32], rpc_url: String) -> Self {
-        let keypair = Keypair::from_bytes(&private_key).expect("Invalid private key");
+    pub fn new(private_key: [u8; 32], rpc_url: String, broker_url: String) -> Result<Self, String> {
+        // `Keypair::from_bytes` wants secret and public halves; derive the public one
+        let secret = SecretKey::from_bytes(&private_key)
+            .map_err(|e| format!("Invalid private key: {}", e))?;
+        let keypair = Keypair { public: PublicKey::from(&secret), secret };
+        Self::with_signer(Box::new(LocalSigner::new(keypair)), rpc_url, broker_url)
+    }
+    
+    /// Sign packets with any backend, e.g. an HSM
+    pub fn with_signer(signer: Box<dyn Signer>, rpc_url: String, broker_url: String) -> Result<Self, String> {
+        let transport = Http::new(&rpc_url)
+            .map_err(|e| format!("Invalid RPC URL {}: {:?}", rpc_url, e))?;
         
-        Self {
-            keypair,
+        Ok(Self {
+            signer,
+            submitter: Box::new(CharmsSubmitter),
             rpc_url,
-            verified_data: Arc::new(Mutex::new(Vec::new())),
-        }
-    }
-    
-    /// Listen to IoT data stream from smart meters
-    pub async fn listen_to_iot_stream(&self, meter_ids: Vec<String>) {
-        // In production: Connect to MQTT/WebSocket stream from smart meters
-        
-        // Simulated data ingestion
-        tokio::spawn(async move {
-            loop {
-                // Receive IoT data (this would be from actual IoT devices)
-                let simulated_data = IoTData {
-                    meter_id: "meter_001".to_string(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis() as u64,
-                    voltage: 230.0,
-                    current: 10.0,
-                    power_factor: 0.95,
-                    cumulative_kwh: 1500.5,
-                };
-                
+            broker_url,
+            blocks: Box::new(Web3::new(transport)),
+            verified_data: Arc::new(Mutex::new(HashMap::new())),
+            next_nonce: Arc::new(Mutex::new(0)),
+            submitted_poe_ids: Arc::new(Mutex::new(HashSet::new())),
+            state_path: None,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            provisioned_meters: Arc::new(Mutex::new(HashMap::new())),
+            last_block_number: Arc::new(Mutex::new(0)),
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            connection_states: Arc::new(Mutex::new(HashMap::new())),
+            last_cumulative_wh: Arc::new(Mutex::new(HashMap::new())),
+            pending_energy_wh: Arc::new(Mutex::new(HashMap::new())),
+            meter_attributes: Arc::new(Mutex::new(HashMap::new())),
+            aggregation_threshold_wh: DEFAULT_AGGREGATION_THRESHOLD_WH,
+            pending_energy_ttl_ms: DEFAULT_PENDING_ENERGY_TTL_MS,
+            last_reading_ms: Arc::new(Mutex::new(HashMap::new())),
+            interpolation: None,
+        })
+    }
+    
+    /// Snapshot of source connection states for the health endpoint
+    pub async fn health(&self) -> api::HealthReport {
+        let sources = self.connection_states.lock().await.clone();
+        
+        api::HealthReport {
+            healthy: sources.values().all(|state| *state == ConnectionState::Connected),
+            sources,
+            verified_count: self.verified_data.lock().await.len(),
+        }
+    }
+    
+    /// Replace how verified readings reach the chain (e.g. a `FaultInjector` in tests)
+    pub fn set_submitter(&mut self, submitter: Box<dyn Submitter>) {
+        self.submitter = submitter;
+    }
+    
+    /// Persist the nonce counter and submitted poe ids to `path`, restoring
+    /// whatever a previous run left there
+    pub async fn set_state_path(&mut self, path: PathBuf) -> Result<(), String> {
+        if path.exists() {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| format!("Failed to read oracle state {}: {}", path.display(), e))?;
+            let state: PersistedState = serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Corrupt oracle state {}: {}", path.display(), e))?;
+            
+            *self.next_nonce.lock().await = state.last_nonce;
+            self.submitted_poe_ids.lock().await.extend(state.submitted_poe_ids);
+        }
+        
+        self.state_path = Some(path);
+        Ok(())
+    }
+    
+    /// Write the dedup state out, replacing the previous file atomically
+    async fn persist_state(&self) -> Result<(), String> {
+        let path = match &self.state_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        
+        let state = PersistedState {
+            last_nonce: *self.next_nonce.lock().await,
+            submitted_poe_ids: self.submitted_poe_ids.lock().await.iter().copied().collect(),
+        };
+        let bytes = serde_json::to_vec(&state).map_err(|e| e.to_string())?;
+        
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, bytes)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Failed to write oracle state {}: {}", path.display(), e))
+    }
+    
+    /// Replace where the current block number comes from (e.g. a mock in tests)
+    pub fn set_block_source(&mut self, blocks: Box<dyn BlockSource>) {
+        self.blocks = blocks;
+    }
+    
+    /// Set how far the block number may regress before submissions are rejected
+    pub fn set_max_reorg_depth(&mut self, depth: u64) {
+        self.max_reorg_depth = depth;
+    }
+    
+    /// Set how much energy a meter must accumulate before one proof covers it
+    pub fn set_aggregation_threshold(&mut self, threshold_wh: u64) {
+        self.aggregation_threshold_wh = threshold_wh;
+    }
+    
+    /// Enable (or with `None` disable) interpolation across skipped intervals
+    pub fn set_interpolation(&mut self, config: Option<InterpolationConfig>) {
+        self.interpolation = config;
+    }
+    
+    /// Set how long accumulated energy may wait for the threshold before it is forfeited
+    pub fn set_pending_energy_ttl(&mut self, ttl_ms: u64) {
+        self.pending_energy_ttl_ms = ttl_ms;
+    }
+    
+    /// Forfeit accumulated energy older than the TTL, e.g. for meters that went
+    /// quiet after their device was suspended. Returns the meters affected.
+    pub async fn expire_pending_energy(&self) -> Vec<String> {
+        let now = iot::now_millis();
+        let mut pending = self.pending_energy_wh.lock().await;
+        
+        let expired: Vec<String> = pending
+            .iter()
+            .filter(|(_, (_, since))| now.saturating_sub(*since) > self.pending_energy_ttl_ms)
+            .map(|(meter_id, _)| meter_id.clone())
+            .collect();
+        
+        for meter_id in &expired {
+            if let Some((energy_wh, since)) = pending.remove(meter_id) {
+                Self::log_forfeit(meter_id, energy_wh, since);
+            }
+        }
+        
+        expired
+    }
+    
+    fn log_forfeit(meter_id: &str, energy_wh: u64, since: u64) {
+        eprintln!(
+            "Forfeited {} Wh pending for meter {} since {} (not proven before expiry)",
+            energy_wh, meter_id, since
+        );
+    }
+    
+    /// Bind a physical source (connection/cert identity) to the meter id it may report
+    pub async fn provision_source(&self, source_id: String, meter_id: String) {
+        self.provisioned_meters.lock().await.insert(source_id, meter_id);
+    }
+    
+    /// Record the energy source and grid zone the oracle attests for a meter
+    pub async fn set_meter_attributes(&self, meter_id: String, energy_source: EnergySource, grid_id: u32) {
+        self.meter_attributes.lock().await.insert(meter_id, (energy_source, grid_id));
+    }
+    
+    /// Agree the newest packet schema version both the oracle and a meter gateway support
+    pub async fn handshake(
+        &self,
+        meter_id: &str,
+        offered_schemas: &[u16],
+    ) -> Result<MeterSession, String> {
+        let schema_version = offered_schemas
+            .iter()
+            .copied()
+            .filter(|v| SUPPORTED_SCHEMA_VERSIONS.contains(v))
+            .max()
+            .ok_or("No common schema version")?;
+        
+        let session = MeterSession { schema_version };
+        self.sessions.lock().await.insert(meter_id.to_string(), session);
+        
+        Ok(session)
+    }
+    
+    /// Session negotiated with a meter (schema 1 if no handshake happened)
+    async fn session_for(&self, meter_id: &str) -> MeterSession {
+        self.sessions.lock().await
+            .get(meter_id)
+            .copied()
+            .unwrap_or_default()
+    }
+    
+    /// Lay out a packet in the meter's negotiated schema version
+    fn build_packet(
+        &self,
+        session: &MeterSession,
+        meter_id: &str,
+        timestamp: u64,
+        energy_wh: u64,
+        cumulative_wh: u128,
+        energy_source: EnergySource,
+        grid_id: u32,
+    ) -> Result<PoEPacket, String> {
+        match session.schema_version {
+            1 => Ok(PoEPacket {
+                device_id: self.hash_meter_id(meter_id),
+                timestamp,
+                energy_wh,
+                cumulative_energy: cumulative_wh,
+                energy_source,
+                grid_id,
+                signature: [0u8; 64], // Not part of the signed bytes
+                oracle_id: self.signer.public_key(),
+            }),
+            version => Err(format!("Unsupported packet schema version {}", version)),
+        }
+    }
+    
+    /// Subscribe to each meter's MQTT topic and process readings as they arrive
+    pub async fn listen_to_iot_stream(self: Arc<Self>, meter_ids: Vec<String>) -> Result<(), String> {
+        for meter_id in meter_ids {
+            let source = MqttMeter::new(meter_id.clone(), &self.broker_url)?;
+            self.provision_source(source.source_id(), meter_id).await;
+            
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.poll_meter(Box::new(source)).await;
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Pull readings from any meter source and process them, reconnecting with
+    /// exponential backoff when the source drops
+    pub async fn poll_meter(&self, mut source: Box<dyn MeterSource>) {
+        let source_id = source.source_id();
+        let mut backoff = INITIAL_RECONNECT_BACKOFF_SECS;
+        let mut attempt = 0u32;
+        
+        loop {
+            match source.next_reading().await {
                 // Verify and sign data
-                self.process_iot_data(simulated_data).await;
+                Ok(reading) => {
+                    attempt = 0;
+                    backoff = INITIAL_RECONNECT_BACKOFF_SECS;
+                    self.set_connection_state(&source_id, ConnectionState::Connected).await;
+                    
+                    if let Err(e) = self.process_iot_data(&source_id, reading).await {
+                        eprintln!("Rejected reading from {}: {}", source_id, e);
+                    }
+                }
+                Err(e) => {
+                    attempt += 1;
+                    eprintln!(
+                        "Failed to read meter {} (attempt {}, retrying in {}s): {}",
+                        source.meter_id(), attempt, backoff, e
+                    );
+                    
+                    let since = match self.connection_states.lock().await.get(&source_id) {
+                        Some(ConnectionState::Reconnecting { since, .. }) => *since,
+                        _ => iot::now_millis(),
+                    };
+                    self.set_connection_state(
+                        &source_id,
+                        ConnectionState::Reconnecting { attempt, since },
+                    ).await;
+                    
+                    tokio::time::sleep(tokio::time::Duration::from_secs(backoff)).await;
+                    backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF_SECS);
+                }
+            }
+        }
+    }
+    
+    async fn set_connection_state(&self, source_id: &str, state: ConnectionState) {
+        self.connection_states.lock().await.insert(source_id.to_string(), state);
+    }
+    
+    async fn process_iot_data(&self, source_id: &str, data: IoTData) -> Result<(), String> {
+        // Only sign for the meter this source was provisioned to report
+        match self.provisioned_meters.lock().await.get(source_id) {
+            Some(meter_id) if *meter_id == data.meter_id => {}
+            Some(meter_id) => {
+                return Err(format!(
+                    "Source provisioned for {} claimed meter {}",
+                    meter_id, data.meter_id
+                ));
+            }
+            None => return Err("Source not provisioned".into()),
+        }
+        
+        let (energy_source, grid_id) = self.meter_attributes.lock().await
+            .get(&data.meter_id)
+            .copied()
+            .ok_or("Meter attributes not configured")?;
+        
+        // The counter only advances once `record_energy` has accounted for the
+        // reading, so a failed submission is re-credited by the next one
+        let cumulative_wh = (data.cumulative_kwh * 1000.0) as u128;
+        let previous_wh = self.last_cumulative_wh.lock().await
+            .get(&data.meter_id)
+            .copied();
+        let previous_ms = self.last_reading_ms.lock().await
+            .get(&data.meter_id)
+            .copied();
+        
+        // Spread a short gap's counter delta over one synthesized reading per
+        // interval instead of crediting it all to this one
+        if let (Some(config), Some(previous_wh), Some(previous_ms)) =
+            (self.interpolation, previous_wh, previous_ms)
+        {
+            if let Some(missed) = config.missed_intervals(previous_ms, data.timestamp) {
+                if missed <= config.max_gap_intervals as u64 {
+                    let delta_wh = cumulative_wh.saturating_sub(previous_wh);
+                    let elapsed = data.timestamp - previous_ms;
+                    let steps = missed + 1;
+                    
+                    let mut credited_to = previous_wh;
+                    for step in 1..=steps {
+                        let timestamp = previous_ms + elapsed * step / steps;
+                        let step_cumulative = previous_wh + delta_wh * step as u128 / steps as u128;
+                        let energy_wh = (step_cumulative - credited_to) as u64;
+                        credited_to = step_cumulative;
+                        
+                        self.record_energy(
+                            &data.meter_id,
+                            timestamp,
+                            energy_wh,
+                            step_cumulative,
+                            energy_source,
+                            grid_id,
+                        ).await?;
+                    }
+                    return Ok(());
+                }
                 
-                tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
+                eprintln!(
+                    "Meter {} skipped {} intervals (max {} interpolated); crediting the gap as one reading",
+                    data.meter_id, missed, config.max_gap_intervals
+                );
             }
-        });
+        }
+        
+        // Spot readings only raise anomaly flags; a glitched power factor must
+        // not zero out energy the counter did record
+        if data.power_factor.abs() < MIN_PLAUSIBLE_POWER_FACTOR {
+            eprintln!(
+                "Meter {} reported implausible power factor {}",
+                data.meter_id, data.power_factor
+            );
+        }
+        
+        // Energy comes from the cumulative counter whenever there is a previous
+        // reading to diff against (including everything accrued across a
+        // dropped connection); only a meter's first reading falls back to a
+        // spot-power estimate
+        let energy_wh = match previous_wh {
+            Some(previous) => cumulative_wh.saturating_sub(previous) as u64,
+            None => (data.voltage * data.current * data.power_factor * 1.0) as u64,
+        };
+        
+        self.record_energy(
+            &data.meter_id,
+            data.timestamp,
+            energy_wh,
+            cumulative_wh,
+            energy_source,
+            grid_id,
+        ).await
     }
     
-    async fn process_iot_data(&self, data: IoTData) {
-        // Calculate energy generated since last reading
-        let energy_wh = (data.voltage * data.current * data.power_factor * 1.0) as u64;
+    /// Accumulate a reading's energy and, once past the threshold, sign and submit
+    /// a packet for it
+    async fn record_energy(
+        &self,
+        meter_id: &str,
+        timestamp: u64,
+        energy_wh: u64,
+        cumulative_wh: u128,
+        energy_source: EnergySource,
+        grid_id: u32,
+    ) -> Result<(), String> {
+        // Accumulate readings until they are worth proving; the packet then
+        // carries the aggregate and the current cumulative counter. Pending
+        // energy is only cleared once that packet has been submitted.
+        let energy_wh = {
+            let now = iot::now_millis();
+            let mut pending = self.pending_energy_wh.lock().await;
+            let (mut accumulated, mut since) = pending.get(meter_id).copied().unwrap_or((0, now));
+            
+            // Energy that sat below the threshold too long is forfeited, not carried
+            if now.saturating_sub(since) > self.pending_energy_ttl_ms {
+                Self::log_forfeit(meter_id, accumulated, since);
+                accumulated = 0;
+                since = now;
+            }
+            
+            accumulated = accumulated.saturating_add(energy_wh);
+            if accumulated < self.aggregation_threshold_wh {
+                pending.insert(meter_id.to_string(), (accumulated, since));
+                drop(pending);
+                self.commit_reading(meter_id, timestamp, cumulative_wh).await;
+                return Ok(());
+            }
+            
+            accumulated
+        };
         
-        // Create PoE packet
-        let poe_packet = serde_json::json!({
-            "device_id": self.hash_meter_id(&data.meter_id),
-            "timestamp": data.timestamp,
-            "energy_wh": energy_wh,
-            "cumulative_energy": (data.cumulative_kwh * 1000.0) as u128,
-        });
+        // Create PoE packet in the schema negotiated with the meter
+        let session = self.session_for(meter_id).await;
+        let poe_packet = self.build_packet(
+            &session,
+            meter_id,
+            timestamp,
+            energy_wh,
+            cumulative_wh,
+            energy_source,
+            grid_id,
+        )?;
         
-        // Sign the packet
-        let message = serde_json::to_vec(&poe_packet).unwrap();
-        let signature = self.keypair.sign(&message).to_bytes();
+        // Sign the canonical packet bytes the minter verifies against
+        let message = packet_signing_bytes(&poe_packet);
+        
+        // A retried reading was already verified and submitted, possibly before a restart
+        let oracle_id = self.signer.public_key();
+        let poe_id = poe_id(&oracle_id, &message);
+        if self.submitted_poe_ids.lock().await.contains(&poe_id) {
+            self.clear_pending(meter_id, timestamp, cumulative_wh).await;
+            return Ok(());
+        }
+        
+        let signature = self.signer.sign(&message).await?;
+        
+        let block_number = self.current_block_number().await;
+        if let Some(block_number) = block_number {
+            self.check_block_monotonic(block_number).await?;
+        }
+        
+        let nonce = {
+            let mut next_nonce = self.next_nonce.lock().await;
+            *next_nonce += 1;
+            *next_nonce
+        };
         
         // Create verified PoE
         let verified_poe = VerifiedPoE {
             packet: message,
             signature,
-            oracle_id: self.keypair.public.to_bytes(),
-            block_number: 0, // Will be set when submitted
+            oracle_id,
+            block_number,
+            nonce,
+            poe_id,
         };
         
+        // Submit to blockchain via Charms SDK
+        self.submit_to_blockchain(&verified_poe).await?;
+        self.clear_pending(meter_id, timestamp, cumulative_wh).await;
+        
         // Store locally
-        let mut verified = self.verified_data.lock().await;
-        verified.push(verified_poe);
+        self.submitted_poe_ids.lock().await.insert(poe_id);
+        self.verified_data.lock().await.entry(poe_id).or_insert(verified_poe);
         
-        // Submit to blockchain via Charms SDK
-        self.submit_to_blockchain(&verified).await;
+        // The reading is on its way on-chain either way; a lost state file only
+        // weakens dedup after the next restart
+        if let Err(e) = self.persist_state().await {
+            eprintln!("{}", e);
+        }
+        
+        Ok(())
+    }
+    
+    /// Advance a meter's counter and reading time to a reading that has been accounted for
+    async fn commit_reading(&self, meter_id: &str, timestamp: u64, cumulative_wh: u128) {
+        self.last_cumulative_wh.lock().await.insert(meter_id.to_string(), cumulative_wh);
+        self.last_reading_ms.lock().await.insert(meter_id.to_string(), timestamp);
+    }
+    
+    /// Drop a meter's pending energy once a packet covering it has been submitted
+    async fn clear_pending(&self, meter_id: &str, timestamp: u64, cumulative_wh: u128) {
+        self.pending_energy_wh.lock().await.remove(meter_id);
+        self.commit_reading(meter_id, timestamp, cumulative_wh).await;
+    }
+    
+    /// Current chain height, or `None` if the RPC is unavailable (record is kept either way)
+    async fn current_block_number(&self) -> Option<u64> {
+        match self.blocks.block_number().await {
+            Ok(number) => Some(number),
+            Err(e) => {
+                eprintln!(
+                    "Failed to fetch block number from {} ({}); flagging the record for reconciliation",
+                    self.rpc_url, e
+                );
+                None
+            }
+        }
+    }
+    
+    /// Reject block numbers that go backwards further than a plausible reorg
+    async fn check_block_monotonic(&self, block_number: u64) -> Result<(), String> {
+        let mut last = self.last_block_number.lock().await;
+        if block_number < *last {
+            let depth = *last - block_number;
+            if depth > self.max_reorg_depth {
+                return Err(format!(
+                    "Block number regressed from {} to {}",
+                    *last, block_number
+                ));
+            }
+            eprintln!("Block number regressed by {} (treated as reorg)", depth);
+        }
+        
+        *last = block_number;
+        Ok(())
     }
     
     fn hash_meter_id(&self, meter_id: &str) -> [u8; 32] {
@@ -110,12 +668,634 @@ impl OracleService {
         result.into()
     }
     
-    async fn submit_to_blockchain(&self, verified_poe: &VerifiedPoE) {
-        // Use Charms SDK to submit to BitcoinOS
-        // This would trigger the minting spell
+    /// Submit with a per-attempt timeout, retrying with exponential backoff
+    async fn submit_to_blockchain(&self, verified_poe: &VerifiedPoE) -> Result<(), String> {
+        let timeout = tokio::time::Duration::from_secs(SUBMIT_TIMEOUT_SECS);
+        let mut backoff = INITIAL_RECONNECT_BACKOFF_SECS;
+        
+        for attempt in 1..=MAX_SUBMIT_ATTEMPTS {
+            let error = match tokio::time::timeout(timeout, self.submitter.submit(verified_poe)).await {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(e)) => e,
+                Err(_) => format!("timed out after {}s", SUBMIT_TIMEOUT_SECS),
+            };
+            
+            eprintln!(
+                "Submission of reading #{} failed (attempt {}/{}): {}",
+                verified_poe.nonce, attempt, MAX_SUBMIT_ATTEMPTS, error
+            );
+            if attempt < MAX_SUBMIT_ATTEMPTS {
+                tokio::time::sleep(tokio::time::Duration::from_secs(backoff)).await;
+                backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF_SECS);
+            }
+        }
+        
+        Err(format!("Submission failed after {} attempts", MAX_SUBMIT_ATTEMPTS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+    use submit::{Fault, FaultInjector};
+    
+    const ORACLE_KEY: [u8; 32] = [7u8; 32];
+    
+    const SOURCE: &str = "source-a";
+    const METER: &str = "meter-a";
+    
+    fn service() -> OracleService {
+        let mut service = OracleService::new(ORACLE_KEY, "http://localhost:8545".into(), "mqtt://localhost:1883".into())
+            .unwrap();
+        service.set_block_source(Box::new(MockBlocks::default()));
+        service
+    }
+    
+    /// Stands in for the web3 RPC; unavailable until a block number is set
+    #[derive(Clone, Default)]
+    struct MockBlocks(Arc<std::sync::Mutex<Option<u64>>>);
+    
+    impl MockBlocks {
+        fn set(&self, block_number: Option<u64>) {
+            *self.0.lock().unwrap() = block_number;
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl BlockSource for MockBlocks {
+        async fn block_number(&self) -> Result<u64, String> {
+            self.0.lock().unwrap().ok_or_else(|| "rpc unavailable".into())
+        }
+    }
+    
+    /// Service proving every 1 kWh whose chain height the test controls
+    fn chain_service() -> (OracleService, MockBlocks) {
+        let blocks = MockBlocks::default();
+        let mut service = service();
+        service.set_aggregation_threshold(1_000);
+        service.set_block_source(Box::new(blocks.clone()));
+        (service, blocks)
+    }
+    
+    /// Record a 1.5 kWh proof at `timestamp`, returning its poe id
+    async fn prove_at(service: &OracleService, timestamp: u64, cumulative_wh: u128) -> Result<[u8; 32], String> {
+        service.record_energy(METER, timestamp, 1_500, cumulative_wh, EnergySource::Solar, 7).await?;
+        Ok(expected_poe_id(service, timestamp, 1_500, cumulative_wh))
+    }
+    
+    async fn block_of(service: &OracleService, poe_id: &[u8; 32]) -> Option<Option<u64>> {
+        service.verified_data.lock().await.get(poe_id).map(|verified| verified.block_number)
+    }
+    
+    /// Service with one provisioned solar meter proving every `threshold_wh`
+    async fn metered_service(threshold_wh: u64) -> OracleService {
+        let mut service = service();
+        service.set_aggregation_threshold(threshold_wh);
+        service.provision_source(SOURCE.into(), METER.into()).await;
+        service.set_meter_attributes(METER.into(), EnergySource::Solar, 7).await;
+        service
+    }
+    
+    /// A counter reading with no spot power, so a meter's first one credits nothing
+    fn reading(timestamp: u64, cumulative_kwh: f64) -> IoTData {
+        IoTData {
+            meter_id: METER.into(),
+            timestamp,
+            voltage: 0.0,
+            current: 0.0,
+            power_factor: 1.0,
+            cumulative_kwh,
+        }
+    }
+    
+    /// Id the service gives a proof of `energy_wh` at this reading
+    fn expected_poe_id(service: &OracleService, timestamp: u64, energy_wh: u64, cumulative_wh: u128) -> [u8; 32] {
+        let packet = service
+            .build_packet(&MeterSession::default(), METER, timestamp, energy_wh, cumulative_wh, EnergySource::Solar, 7)
+            .unwrap();
+        poe_id(&service.signer.public_key(), &packet_signing_bytes(&packet))
+    }
+    
+    async fn pending(service: &OracleService) -> Option<u64> {
+        service.pending_energy_wh.lock().await.get(METER).map(|(energy_wh, _)| *energy_wh)
+    }
+    
+    async fn committed_counter(service: &OracleService) -> Option<u128> {
+        service.last_cumulative_wh.lock().await.get(METER).copied()
+    }
+    
+    /// Backdate when the meter's pending energy started accumulating
+    async fn age_pending(service: &OracleService, age_ms: u64) {
+        if let Some((_, since)) = service.pending_energy_wh.lock().await.get_mut(METER) {
+            *since = iot::now_millis() - age_ms;
+        }
+    }
+    
+    /// Lets a test keep counting attempts on an injector the service owns
+    struct Shared(Arc<FaultInjector<CharmsSubmitter>>);
+    
+    #[async_trait::async_trait]
+    impl Submitter for Shared {
+        async fn submit(&self, verified_poe: &VerifiedPoE) -> Result<(), String> {
+            self.0.submit(verified_poe).await
+        }
+    }
+    
+    fn faulty_service(fault: Fault, fail_on: Vec<u32>) -> (OracleService, Arc<FaultInjector<CharmsSubmitter>>) {
+        let injector = Arc::new(FaultInjector::new(CharmsSubmitter, fault, fail_on));
+        let mut service = service();
+        service.set_submitter(Box::new(Shared(Arc::clone(&injector))));
+        (service, injector)
+    }
+    
+    /// Service that counts submissions and keeps its dedup state in `path`
+    async fn persisted_service(path: &PathBuf) -> (OracleService, Arc<FaultInjector<CharmsSubmitter>>) {
+        let (mut service, injector) = faulty_service(Fault::Fail, vec![]);
+        service.set_aggregation_threshold(1_000);
+        service.set_state_path(path.clone()).await.unwrap();
+        (service, injector)
+    }
+    
+    /// State file path no earlier run has left behind
+    fn state_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("oracle-state-{}-{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+    
+    async fn nonce_of(service: &OracleService, poe_id: &[u8; 32]) -> Option<u64> {
+        service.verified_data.lock().await.get(poe_id).map(|verified| verified.nonce)
+    }
+    
+    fn verified_poe(nonce: u64) -> VerifiedPoE {
+        VerifiedPoE {
+            packet: vec![1, 2, 3],
+            signature: [0u8; 64],
+            oracle_id: [0u8; 32],
+            block_number: None,
+            nonce,
+            poe_id: [nonce as u8; 32],
+        }
+    }
+    
+    #[tokio::test]
+    async fn handshake_agrees_on_newest_common_schema() {
+        let service = service();
+        
+        assert_eq!(service.handshake("meter-a", &[1, 2]).await, Ok(MeterSession { schema_version: 1 }));
+        assert_eq!(service.session_for("meter-a").await, MeterSession { schema_version: 1 });
+    }
+    
+    #[tokio::test]
+    async fn handshake_rejects_unsupported_schema_versions() {
+        let service = service();
+        
+        assert_eq!(
+            service.handshake("meter-b", &[2]).await,
+            Err("No common schema version".into())
+        );
+        assert!(service.sessions.lock().await.get("meter-b").is_none());
+        
+        let unsupported = MeterSession { schema_version: 2 };
+        assert_eq!(
+            service.build_packet(&unsupported, "meter-b", 1_000, 500, 1_500, EnergySource::Solar, 7).err(),
+            Some("Unsupported packet schema version 2".into())
+        );
+    }
+    
+    #[tokio::test]
+    async fn negotiated_packets_verify_against_oracle_id() {
+        let service = service();
+        let session = service.handshake("meter-a", &[1]).await.unwrap();
+        
+        let packet = service
+            .build_packet(&session, "meter-a", 1_000, 500, 1_500, EnergySource::Solar, 7)
+            .unwrap();
+        let message = packet_signing_bytes(&packet);
+        let signature = service.signer.sign(&message).await.unwrap();
+        
+        let public_key = PublicKey::from_bytes(&packet.oracle_id).unwrap();
+        assert!(public_key.verify(&message, &Signature::from_bytes(&signature).unwrap()).is_ok());
+    }
+    
+    #[tokio::test]
+    async fn readings_below_threshold_accumulate_without_submitting() {
+        let service = metered_service(1_000).await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(2_000, 1.4)).await.unwrap();
+        
+        assert_eq!(pending(&service).await, Some(400));
+        assert_eq!(committed_counter(&service).await, Some(1_400));
+        assert!(service.verified_data.lock().await.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn crossing_threshold_submits_one_proof_for_the_aggregate() {
+        let service = metered_service(1_000).await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(2_000, 1.4)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(3_000, 2.1)).await.unwrap();
+        
+        let verified = service.verified_data.lock().await;
+        assert_eq!(verified.len(), 1);
+        assert!(verified.contains_key(&expected_poe_id(&service, 3_000, 1_100, 2_100)));
+        drop(verified);
+        
+        assert_eq!(pending(&service).await, None);
+        assert_eq!(committed_counter(&service).await, Some(2_100));
+    }
+    
+    #[tokio::test]
+    async fn pending_energy_is_proven_before_it_expires() {
+        let mut service = metered_service(1_000).await;
+        service.set_pending_energy_ttl(60_000);
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(2_000, 1.6)).await.unwrap();
+        age_pending(&service, 30_000).await;
+        service.process_iot_data(SOURCE, reading(3_000, 2.1)).await.unwrap();
+        
+        assert!(service.verified_data.lock().await.contains_key(&expected_poe_id(&service, 3_000, 1_100, 2_100)));
+        assert_eq!(pending(&service).await, None);
+    }
+    
+    #[tokio::test]
+    async fn pending_energy_is_forfeited_after_it_expires() {
+        let mut service = metered_service(1_000).await;
+        service.set_pending_energy_ttl(60_000);
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(2_000, 1.6)).await.unwrap();
+        age_pending(&service, 120_000).await;
+        
+        // Only the new reading's energy remains, still short of a proof
+        service.process_iot_data(SOURCE, reading(3_000, 2.1)).await.unwrap();
+        assert_eq!(pending(&service).await, Some(500));
+        assert!(service.verified_data.lock().await.is_empty());
+        
+        // The keeper sweep forfeits meters that went quiet
+        age_pending(&service, 120_000).await;
+        assert_eq!(service.expire_pending_energy().await, vec![METER.to_string()]);
+        assert_eq!(pending(&service).await, None);
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn failed_submission_leaves_counter_and_pending_energy_for_the_next_reading() {
+        let mut service = metered_service(1_000).await;
+        let failing = (1..=MAX_SUBMIT_ATTEMPTS).collect();
+        service.set_submitter(Box::new(FaultInjector::new(CharmsSubmitter, Fault::Fail, failing)));
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(2_000, 1.4)).await.unwrap();
+        assert!(service.process_iot_data(SOURCE, reading(3_000, 2.1)).await.is_err());
+        
+        assert_eq!(pending(&service).await, Some(400));
+        assert_eq!(committed_counter(&service).await, Some(1_400));
+        assert!(service.verified_data.lock().await.is_empty());
+        
+        // The next reading re-credits everything since the last committed counter
+        service.process_iot_data(SOURCE, reading(4_000, 2.3)).await.unwrap();
+        
+        assert!(service.verified_data.lock().await.contains_key(&expected_poe_id(&service, 4_000, 1_300, 2_300)));
+        assert_eq!(pending(&service).await, None);
+        assert_eq!(committed_counter(&service).await, Some(2_300));
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn submission_retries_past_a_failure_on_the_nth_attempt() {
+        let (service, injector) = faulty_service(Fault::Fail, vec![1, 2]);
+        
+        assert_eq!(service.submit_to_blockchain(&verified_poe(1)).await, Ok(()));
+        assert_eq!(injector.attempts(), 3);
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn submission_retries_an_attempt_that_times_out() {
+        let hang = tokio::time::Duration::from_secs(SUBMIT_TIMEOUT_SECS + 1);
+        let (service, injector) = faulty_service(Fault::Timeout(hang), vec![1]);
+        
+        assert_eq!(service.submit_to_blockchain(&verified_poe(1)).await, Ok(()));
+        assert_eq!(injector.attempts(), 2);
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn submission_gives_up_after_max_attempts_then_recovers() {
+        let failing = (1..=MAX_SUBMIT_ATTEMPTS).collect();
+        let (service, injector) = faulty_service(Fault::Error("rpc unavailable".into()), failing);
+        
+        assert_eq!(
+            service.submit_to_blockchain(&verified_poe(1)).await,
+            Err(format!("Submission failed after {} attempts", MAX_SUBMIT_ATTEMPTS))
+        );
+        assert_eq!(injector.attempts(), MAX_SUBMIT_ATTEMPTS);
+        
+        // Once the fault clears, the next reading goes through first time
+        assert_eq!(service.submit_to_blockchain(&verified_poe(2)).await, Ok(()));
+        assert_eq!(injector.attempts(), MAX_SUBMIT_ATTEMPTS + 1);
+    }
+    
+    #[tokio::test]
+    async fn retries_of_a_reading_share_a_poe_id_and_are_deduped() {
+        let path = state_file("retry");
+        let (service, injector) = persisted_service(&path).await;
+        let poe_id = expected_poe_id(&service, 1_000, 1_500, 1_500);
+        
+        for _ in 0..2 {
+            service.record_energy(METER, 1_000, 1_500, 1_500, EnergySource::Solar, 7).await.unwrap();
+        }
+        
+        assert_eq!(injector.attempts(), 1);
+        assert_eq!(nonce_of(&service, &poe_id).await, Some(1));
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[tokio::test]
+    async fn dedup_and_nonce_survive_a_restart() {
+        let path = state_file("restart");
+        let (service, _) = persisted_service(&path).await;
+        service.record_energy(METER, 1_000, 1_500, 1_500, EnergySource::Solar, 7).await.unwrap();
+        drop(service);
+        
+        // The retry after a restart is recognised and not submitted again
+        let (restarted, injector) = persisted_service(&path).await;
+        restarted.record_energy(METER, 1_000, 1_500, 1_500, EnergySource::Solar, 7).await.unwrap();
+        assert_eq!(injector.attempts(), 0);
+        
+        // New readings carry on from the persisted nonce
+        restarted.record_energy(METER, 2_000, 1_500, 3_000, EnergySource::Solar, 7).await.unwrap();
+        assert_eq!(injector.attempts(), 1);
+        assert_eq!(nonce_of(&restarted, &expected_poe_id(&restarted, 2_000, 1_500, 3_000)).await, Some(2));
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[tokio::test]
+    async fn block_number_from_rpc_is_recorded() {
+        let (service, blocks) = chain_service();
+        blocks.set(Some(1_234));
+        
+        let poe_id = prove_at(&service, 1_000, 1_500).await.unwrap();
+        assert_eq!(block_of(&service, &poe_id).await, Some(Some(1_234)));
+    }
+    
+    #[tokio::test]
+    async fn rpc_failure_keeps_the_record_flagged_without_a_block() {
+        let (service, blocks) = chain_service();
+        blocks.set(Some(100));
+        prove_at(&service, 1_000, 1_500).await.unwrap();
+        
+        blocks.set(None);
+        let poe_id = prove_at(&service, 2_000, 3_000).await.unwrap();
+        assert_eq!(block_of(&service, &poe_id).await, Some(None));
+        
+        // The outage does not reset the height later blocks are compared against
+        assert_eq!(*service.last_block_number.lock().await, 100);
+    }
+    
+    #[tokio::test]
+    async fn monotonic_block_numbers_are_accepted() {
+        let (service, blocks) = chain_service();
+        
+        for (i, block_number) in [100u64, 100, 101].into_iter().enumerate() {
+            blocks.set(Some(block_number));
+            let step = i as u64 + 1;
+            let poe_id = prove_at(&service, step * 1_000, step as u128 * 1_500).await.unwrap();
+            assert_eq!(block_of(&service, &poe_id).await, Some(Some(block_number)));
+        }
+    }
+    
+    #[tokio::test]
+    async fn regressing_block_number_is_flagged_past_the_reorg_depth() {
+        let (mut service, blocks) = chain_service();
+        service.set_max_reorg_depth(6);
+        
+        blocks.set(Some(100));
+        prove_at(&service, 1_000, 1_500).await.unwrap();
+        
+        // A shallow regression is a reorg and still accepted
+        blocks.set(Some(97));
+        let poe_id = prove_at(&service, 2_000, 3_000).await.unwrap();
+        assert_eq!(block_of(&service, &poe_id).await, Some(Some(97)));
+        
+        blocks.set(Some(90));
+        assert_eq!(
+            prove_at(&service, 3_000, 4_500).await,
+            Err("Block number regressed from 97 to 90".into())
+        );
+        assert_eq!(service.verified_data.lock().await.len(), 2);
+        assert_eq!(committed_counter(&service).await, Some(3_000));
+    }
+    
+    /// Records every message it is asked to sign
+    struct MockSigner(Arc<std::sync::Mutex<Vec<Vec<u8>>>>);
+    
+    #[async_trait::async_trait]
+    impl Signer for MockSigner {
+        fn public_key(&self) -> [u8; 32] {
+            [3u8; 32]
+        }
+        
+        async fn sign(&self, message: &[u8]) -> Result<[u8; 64], String> {
+            self.0.lock().unwrap().push(message.to_vec());
+            Ok([9u8; 64])
+        }
+    }
+    
+    #[tokio::test]
+    async fn packets_are_signed_through_the_configured_signer() {
+        let messages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut service = OracleService::with_signer(
+            Box::new(MockSigner(Arc::clone(&messages))),
+            "http://localhost:8545".into(),
+            "mqtt://localhost:1883".into(),
+        ).unwrap();
+        service.set_block_source(Box::new(MockBlocks::default()));
+        service.set_aggregation_threshold(1_000);
+        
+        service.record_energy(METER, 1_000, 1_500, 1_500, EnergySource::Solar, 7).await.unwrap();
+        
+        let packet = service
+            .build_packet(&MeterSession::default(), METER, 1_000, 1_500, 1_500, EnergySource::Solar, 7)
+            .unwrap();
+        assert_eq!(packet.oracle_id, [3u8; 32]);
+        assert_eq!(*messages.lock().unwrap(), vec![packet_signing_bytes(&packet)]);
+        
+        let verified = service.verified_data.lock().await;
+        let verified = verified.get(&expected_poe_id(&service, 1_000, 1_500, 1_500)).unwrap();
+        assert_eq!(verified.signature, [9u8; 64]);
+        assert_eq!(verified.oracle_id, [3u8; 32]);
+    }
+    
+    #[test]
+    fn invalid_rpc_url_is_an_error() {
+        let result = OracleService::new(ORACLE_KEY, "not a url".into(), "mqtt://localhost:1883".into());
+        assert!(result.err().unwrap().starts_with("Invalid RPC URL not a url"));
+    }
+    
+    /// Metered service interpolating gaps of up to two missed 1 s intervals
+    async fn interpolating_service() -> OracleService {
+        let mut service = metered_service(1_000).await;
+        service.set_interpolation(Some(InterpolationConfig { interval_ms: 1_000, max_gap_intervals: 2 }));
+        service
+    }
+    
+    #[test]
+    fn missed_intervals_tolerate_jitter() {
+        let config = InterpolationConfig { interval_ms: 1_000, max_gap_intervals: 2 };
+        
+        assert_eq!(config.missed_intervals(1_000, 2_400), None);
+        assert_eq!(config.missed_intervals(1_000, 3_100), Some(1));
+        assert_eq!(config.missed_intervals(1_000, 6_000), Some(4));
+    }
+    
+    #[tokio::test]
+    async fn single_interval_gap_is_interpolated() {
+        let service = interpolating_service().await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(3_000, 3.0)).await.unwrap();
+        
+        // The missed interval gets its own signed packet for half the delta
+        let verified = service.verified_data.lock().await;
+        assert_eq!(verified.len(), 2);
+        assert!(verified.contains_key(&expected_poe_id(&service, 2_000, 1_000, 2_000)));
+        assert!(verified.contains_key(&expected_poe_id(&service, 3_000, 1_000, 3_000)));
+        drop(verified);
+        
+        assert_eq!(committed_counter(&service).await, Some(3_000));
+    }
+    
+    #[tokio::test]
+    async fn long_gap_is_flagged_instead_of_interpolated() {
+        let service = interpolating_service().await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(6_000, 6.0)).await.unwrap();
+        
+        // Four missed intervals exceed the bound: one packet carries the whole gap
+        let verified = service.verified_data.lock().await;
+        assert_eq!(verified.len(), 1);
+        assert!(verified.contains_key(&expected_poe_id(&service, 6_000, 5_000, 6_000)));
+    }
+    
+    #[tokio::test]
+    async fn gaps_are_credited_whole_without_interpolation() {
+        let service = metered_service(1_000).await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, reading(3_000, 3.0)).await.unwrap();
+        
+        let verified = service.verified_data.lock().await;
+        assert_eq!(verified.len(), 1);
+        assert!(verified.contains_key(&expected_poe_id(&service, 3_000, 2_000, 3_000)));
+    }
+    
+    #[tokio::test]
+    async fn readings_are_only_signed_for_the_provisioned_meter() {
+        let service = metered_service(1_000).await;
+        
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        assert_eq!(committed_counter(&service).await, Some(1_000));
+        
+        // The same source claiming another meter's id
+        let spoofed = IoTData { meter_id: "meter-b".into(), ..reading(2_000, 9.0) };
+        assert_eq!(
+            service.process_iot_data(SOURCE, spoofed).await,
+            Err("Source provisioned for meter-a claimed meter meter-b".into())
+        );
+        assert_eq!(
+            service.process_iot_data("source-b", reading(2_000, 9.0)).await,
+            Err("Source not provisioned".into())
+        );
+        
+        assert_eq!(committed_counter(&service).await, Some(1_000));
+        assert!(service.last_cumulative_wh.lock().await.get("meter-b").is_none());
+    }
+    
+    /// Replays scripted readings and drops, then goes quiet
+    struct ScriptedMeter(std::collections::VecDeque<Result<IoTData, String>>);
+    
+    #[async_trait::async_trait]
+    impl MeterSource for ScriptedMeter {
+        fn meter_id(&self) -> &str {
+            METER
+        }
+        
+        fn source_id(&self) -> String {
+            SOURCE.into()
+        }
+        
+        async fn next_reading(&mut self) -> Result<IoTData, String> {
+            match self.0.pop_front() {
+                Some(next) => next,
+                None => std::future::pending().await,
+            }
+        }
+    }
+    
+    /// Poll `script` until it runs out (the poll loop itself never returns)
+    async fn poll_script(service: &OracleService, script: Vec<Result<IoTData, String>>) {
+        let polling = service.poll_meter(Box::new(ScriptedMeter(script.into())));
+        let timeout = tokio::time::Duration::from_secs(MAX_RECONNECT_BACKOFF_SECS * 10);
+        assert!(tokio::time::timeout(timeout, polling).await.is_err());
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn dropped_source_is_reported_as_reconnecting() {
+        let service = metered_service(1_000).await;
+        
+        poll_script(&service, vec![
+            Ok(reading(1_000, 1.0)),
+            Err("connection reset".into()),
+            Err("connection refused".into()),
+        ]).await;
+        
+        let health = service.health().await;
+        assert!(!health.healthy);
+        assert!(matches!(
+            health.sources.get(SOURCE),
+            Some(ConnectionState::Reconnecting { attempt: 2, .. })
+        ));
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn reconnected_source_backfills_the_missed_delta() {
+        let service = metered_service(1_000).await;
+        
+        poll_script(&service, vec![
+            Ok(reading(1_000, 1.0)),
+            Err("connection reset".into()),
+            Err("connection refused".into()),
+            Ok(reading(5_000, 2.5)),
+        ]).await;
+        
+        // Everything the meter counted while disconnected lands in one proof
+        assert!(service.verified_data.lock().await.contains_key(&expected_poe_id(&service, 5_000, 1_500, 2_500)));
+        assert_eq!(committed_counter(&service).await, Some(2_500));
+        
+        let health = service.health().await;
+        assert!(health.healthy);
+        assert_eq!(health.sources.get(SOURCE), Some(&ConnectionState::Connected));
+    }
+    
+    #[tokio::test]
+    async fn counter_energy_ignores_a_glitched_power_factor() {
+        let service = metered_service(1_000).await;
+        let glitched = |timestamp, cumulative_kwh| IoTData {
+            voltage: 230.0,
+            current: 10.0,
+            power_factor: 0.0001,
+            ..reading(timestamp, cumulative_kwh)
+        };
         
-        println!("Submitting verified PoE to blockchain: {:?}", verified_poe);
+        service.process_iot_data(SOURCE, reading(1_000, 1.0)).await.unwrap();
+        service.process_iot_data(SOURCE, glitched(2_000, 1.4)).await.unwrap();
+        assert_eq!(pending(&service).await, Some(400));
         
-        // In production: Call Charms API or smart contract
+        service.process_iot_data(SOURCE, glitched(3_000, 2.1)).await.unwrap();
+        assert!(service.verified_data.lock().await.contains_key(&expected_poe_id(&service, 3_000, 1_100, 2_100)));
+        assert_eq!(committed_counter(&service).await, Some(2_100));
     }
 }
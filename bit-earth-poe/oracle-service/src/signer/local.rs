@@ -0,0 +1,27 @@
+//! In-memory Ed25519 signer
+
+use async_trait::async_trait;
+use ed25519_dalek::{Keypair, Signer as _};
+
+use super::Signer;
+
+pub struct LocalSigner {
+    keypair: Keypair,
+}
+
+impl LocalSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.keypair.public.to_bytes()
+    }
+    
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64], String> {
+        Ok(self.keypair.sign(message).to_bytes())
+    }
+}
@@ -0,0 +1,20 @@
+//! Oracle signing backends
+//! The service signs packets through `Signer` so keys can live in an HSM/KMS
+
+use async_trait::async_trait;
+
+pub mod local;
+pub mod pkcs11;
+
+pub use local::LocalSigner;
+pub use pkcs11::Pkcs11Signer;
+
+/// Ed25519 signer for oracle packets (in-memory key, PKCS#11 token, ...)
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Ed25519 public key; doubles as the oracle id on the minter
+    fn public_key(&self) -> [u8; 32];
+    
+    /// Sign `message`, returning the 64-byte Ed25519 signature
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64], String>;
+}
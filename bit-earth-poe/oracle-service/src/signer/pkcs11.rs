@@ -0,0 +1,104 @@
+//! PKCS#11 signer
+//! Signs with an Ed25519 key held on a hardware token or a cloud HSM that
+//! exposes a PKCS#11 module (AWS CloudHSM, YubiHSM, SoftHSM for testing, ...)
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+
+use super::Signer;
+
+pub struct Pkcs11Signer {
+    // Sessions aren't safe to share between threads
+    session: Mutex<Session>,
+    private_key: ObjectHandle,
+    public_key: [u8; 32],
+}
+
+impl Pkcs11Signer {
+    /// Open a session on the first token holding a key pair labelled `key_label`
+    pub fn new(module_path: &str, pin: &str, key_label: &str) -> Result<Self, String> {
+        let pkcs11 = Pkcs11::new(module_path)
+            .map_err(|e| format!("Failed to load PKCS#11 module {}: {}", module_path, e))?;
+        pkcs11
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(|e| format!("Failed to initialize PKCS#11 module: {}", e))?;
+        
+        let slot = pkcs11
+            .get_slots_with_token()
+            .map_err(|e| format!("Failed to list PKCS#11 slots: {}", e))?
+            .into_iter()
+            .next()
+            .ok_or("No PKCS#11 token present")?;
+        
+        let session = pkcs11
+            .open_ro_session(slot)
+            .map_err(|e| format!("Failed to open PKCS#11 session: {}", e))?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.into())))
+            .map_err(|e| format!("PKCS#11 login failed: {}", e))?;
+        
+        let private_key = Self::find_key(&session, ObjectClass::PRIVATE_KEY, key_label)?;
+        let public_handle = Self::find_key(&session, ObjectClass::PUBLIC_KEY, key_label)?;
+        let public_key = Self::read_public_key(&session, public_handle)?;
+        
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            public_key,
+        })
+    }
+    
+    fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle, String> {
+        session
+            .find_objects(&[Attribute::Class(class), Attribute::Label(label.as_bytes().to_vec())])
+            .map_err(|e| format!("PKCS#11 key lookup failed: {}", e))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("No {:?} labelled {} on token", class, label))
+    }
+    
+    /// Ed25519 public keys are stored as a DER OCTET STRING in `CKA_EC_POINT`
+    fn read_public_key(session: &Session, handle: ObjectHandle) -> Result<[u8; 32], String> {
+        let attributes = session
+            .get_attributes(handle, &[AttributeType::EcPoint])
+            .map_err(|e| format!("Failed to read public key: {}", e))?;
+        
+        let point = attributes
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::EcPoint(point) => Some(point),
+                _ => None,
+            })
+            .ok_or("Public key has no EC point")?;
+        
+        match point.as_slice() {
+            [0x04, 0x20, key @ ..] if key.len() == 32 => Ok(key.try_into().unwrap()),
+            key if key.len() == 32 => Ok(key.try_into().unwrap()),
+            _ => Err("Public key is not an Ed25519 point".into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for Pkcs11Signer {
+    fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+    
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64], String> {
+        let session = self.session.lock().map_err(|_| "PKCS#11 session poisoned")?;
+        let signature = session
+            .sign(&Mechanism::Eddsa, self.private_key, message)
+            .map_err(|e| format!("PKCS#11 signing failed: {}", e))?;
+        
+        signature
+            .try_into()
+            .map_err(|_| "PKCS#11 returned a malformed signature".into())
+    }
+}
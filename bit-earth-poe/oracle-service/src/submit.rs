@@ -0,0 +1,96 @@
+//! Submission of verified readings to the chain
+//! The service submits through `Submitter`; tests can wrap it in a `FaultInjector`
+
+use async_trait::async_trait;
+
+use crate::VerifiedPoE;
+
+/// Sends a verified reading on to the minter
+#[async_trait]
+pub trait Submitter: Send + Sync {
+    async fn submit(&self, verified_poe: &VerifiedPoE) -> Result<(), String>;
+}
+
+/// Submits through the Charms SDK to BitcoinOS
+pub struct CharmsSubmitter;
+
+#[async_trait]
+impl Submitter for CharmsSubmitter {
+    async fn submit(&self, verified_poe: &VerifiedPoE) -> Result<(), String> {
+        // Use Charms SDK to submit to BitcoinOS
+        // This would trigger the minting spell
+        
+        eprintln!("Submitting verified PoE to blockchain: {:?}", verified_poe);
+        
+        // In production: Call Charms API or smart contract
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub use fault::{Fault, FaultInjector};
+
+#[cfg(test)]
+mod fault {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    
+    use async_trait::async_trait;
+    use tokio::time::Duration;
+    
+    use super::Submitter;
+    use crate::VerifiedPoE;
+    
+    /// Failure to force on a submission attempt
+    #[derive(Debug, Clone)]
+    pub enum Fault {
+        /// Fail with a generic error
+        Fail,
+        /// Hang for this long before delegating (outlasting the submit timeout)
+        Timeout(Duration),
+        /// Fail with this exact error
+        Error(String),
+    }
+    
+    /// Wraps a submitter and injects `fault` on chosen attempts (1-based)
+    pub struct FaultInjector<S> {
+        inner: S,
+        fault: Fault,
+        fail_on: Vec<u32>,
+        attempts: AtomicU32,
+    }
+    
+    impl<S: Submitter> FaultInjector<S> {
+        pub fn new(inner: S, fault: Fault, fail_on: Vec<u32>) -> Self {
+            Self {
+                inner,
+                fault,
+                fail_on,
+                attempts: AtomicU32::new(0),
+            }
+        }
+        
+        /// Submission attempts seen so far
+        pub fn attempts(&self) -> u32 {
+            self.attempts.load(Ordering::SeqCst)
+        }
+    }
+    
+    #[async_trait]
+    impl<S: Submitter> Submitter for FaultInjector<S> {
+        async fn submit(&self, verified_poe: &VerifiedPoE) -> Result<(), String> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if !self.fail_on.contains(&attempt) {
+                return self.inner.submit(verified_poe).await;
+            }
+            
+            match &self.fault {
+                Fault::Fail => Err(format!("Injected failure on attempt {}", attempt)),
+                Fault::Timeout(delay) => {
+                    tokio::time::sleep(*delay).await;
+                    self.inner.submit(verified_poe).await
+                }
+                Fault::Error(error) => Err(error.clone()),
+            }
+        }
+    }
+}
@@ -64,6 +64,7 @@ UTXO_ADDRESS=$(charms deploy \
     --wasm spells/utxo_verifier.wasm \
     --network testnet \
     --name "UTXOVerifier" \
+    --args "$ADMIN_WALLET" \
     --output-json | jq -r '.contract_address')
 
 # Register spells